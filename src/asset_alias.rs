@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AliasError {
+    #[error("Failed to read alias table")]
+    Io(#[from] io::Error),
+    #[error("Failed to parse alias table")]
+    Json(#[from] serde_json::Error)
+}
+
+// Logical asset names mapped to real paths, e.g. {"hero": "textures/hero_v2.png"}
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct AliasTable {
+    aliases: HashMap<String, String>
+}
+
+impl AliasTable {
+    pub fn from_json(json: &str) -> Result<Self, AliasError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    // A game without an alias file just gets an empty table
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, AliasError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into())
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.aliases.get(name).map(String::as_str)
+    }
+
+    // Names without an alias are already real paths and come back unchanged
+    pub fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        self.get(name).unwrap_or(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sprite_render_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Failed to create temp dir");
+
+        dir
+    }
+
+    #[test]
+    fn alias_resolves_to_underlying_file() {
+        let dir = temp_dir("alias");
+        let texture = dir.join("hero_v2.png");
+        fs::write(&texture, b"hero pixels").unwrap();
+        fs::write(
+            dir.join("aliases.json"),
+            serde_json::json!({ "hero": texture.to_string_lossy() }).to_string()
+        ).unwrap();
+
+        let aliases = AliasTable::load(dir.join("aliases.json")).unwrap();
+
+        assert_eq!(fs::read(aliases.resolve("hero")).unwrap(), b"hero pixels");
+        assert_eq!(aliases.resolve("textures/villain.png"), "textures/villain.png");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_alias_file_is_empty_and_bad_json_fails() {
+        let dir = temp_dir("alias_missing");
+
+        assert_eq!(AliasTable::load(dir.join("aliases.json")).unwrap(), AliasTable::default());
+        assert!(AliasTable::from_json(r#"{ "hero": 3 }"#).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasPackError {
+    #[error("Image {index} is {width}px wide, which does not fit a {max_width}px atlas with its padding")]
+    TooWide { index: usize, width: u32, max_width: u32 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedAtlas {
+    pub width: u32,
+    pub height: u32,
+    // One region per input size, in input order
+    pub regions: Vec<PackedRegion>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasPacker {
+    pub max_width: u32,
+    // Empty pixels kept around every image, including along the atlas edges, so filtering never bleeds neighbours in
+    pub padding: u32
+}
+
+impl AtlasPacker {
+    // Shelf packing: tallest images first, filled left to right into rows
+    pub fn pack(&self, sizes: &[[u32; 2]]) -> Result<PackedAtlas, AtlasPackError> {
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(sizes[i][1]));
+
+        let mut regions = vec![PackedRegion { x: 0, y: 0, width: 0, height: 0 }; sizes.len()];
+        let (mut x, mut y) = (self.padding, self.padding);
+        let mut shelf_height = 0;
+        let mut width = 0;
+
+        for index in order {
+            let [image_width, image_height] = sizes[index];
+
+            if image_width + 2 * self.padding > self.max_width {
+                return Err(AtlasPackError::TooWide { index, width: image_width, max_width: self.max_width })
+            }
+
+            if x + image_width + self.padding > self.max_width {
+                x = self.padding;
+                y += shelf_height + self.padding;
+                shelf_height = 0;
+            }
+
+            regions[index] = PackedRegion { x, y, width: image_width, height: image_height };
+
+            x += image_width + self.padding;
+            width = width.max(x);
+            shelf_height = shelf_height.max(image_height);
+        }
+
+        Ok(PackedAtlas {
+            width,
+            height: if sizes.is_empty() { 0 } else { y + shelf_height + self.padding },
+            regions
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // True when the two regions keep at least `gap` empty pixels between them on some axis
+    fn separated(a: &PackedRegion, b: &PackedRegion, gap: u32) -> bool {
+        a.x + a.width + gap <= b.x
+            || b.x + b.width + gap <= a.x
+            || a.y + a.height + gap <= b.y
+            || b.y + b.height + gap <= a.y
+    }
+
+    #[test]
+    fn packs_three_images_without_overlap_and_with_padding() {
+        let packer = AtlasPacker { max_width: 48, padding: 2 };
+        let sizes = [[16, 16], [32, 8], [8, 24]];
+
+        let atlas = packer.pack(&sizes).unwrap();
+
+        assert_eq!(atlas.regions.len(), 3);
+        for (region, size) in atlas.regions.iter().zip(sizes.iter()) {
+            assert_eq!([region.width, region.height], *size);
+            assert!(region.x >= 2 && region.y >= 2);
+            assert!(region.x + region.width + 2 <= atlas.width);
+            assert!(region.y + region.height + 2 <= atlas.height);
+        }
+        assert!(atlas.width <= 48);
+
+        for (i, a) in atlas.regions.iter().enumerate() {
+            for b in &atlas.regions[i + 1..] {
+                assert!(separated(a, b, 2), "{:?} and {:?} are closer than the padding", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_image_wider_than_atlas() {
+        let packer = AtlasPacker { max_width: 32, padding: 2 };
+
+        assert_eq!(
+            packer.pack(&[[8, 8], [30, 8]]),
+            Err(AtlasPackError::TooWide { index: 1, width: 30, max_width: 32 })
+        );
+    }
+
+    #[test]
+    fn empty_input_packs_to_empty_atlas() {
+        let atlas = AtlasPacker { max_width: 32, padding: 2 }.pack(&[]).unwrap();
+
+        assert_eq!((atlas.width, atlas.height), (0, 0));
+        assert!(atlas.regions.is_empty());
+    }
+}
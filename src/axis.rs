@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+// Holding both keys cancels out rather than favouring whichever was pressed last
+pub fn axis(negative_held: bool, positive_held: bool) -> f32 {
+    match (negative_held, positive_held) {
+        (false, true) => 1.0,
+        (true, false) => -1.0,
+        _ => 0.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositeAxis {
+    pub negative: String,
+    pub positive: String
+}
+
+impl CompositeAxis {
+    pub fn value<S: AsRef<str>>(&self, held: &[S]) -> f32 {
+        let is_held = |action: &str| held.iter().any(|h| h.as_ref() == action);
+
+        axis(is_held(&self.negative), is_held(&self.positive))
+    }
+}
+
+// Named axes such as "horizontal" -> (left, right), read with the actions held this frame
+#[derive(Debug, Clone, Default)]
+pub struct AxisMap {
+    axes: HashMap<String, CompositeAxis>
+}
+
+impl AxisMap {
+    pub fn bind(&mut self, name: &str, negative: &str, positive: &str) {
+        self.axes.insert(name.to_string(), CompositeAxis {
+            negative: negative.to_string(),
+            positive: positive.to_string()
+        });
+    }
+
+    // An unbound axis reads as centred
+    pub fn axis<S: AsRef<str>>(&self, name: &str, held: &[S]) -> f32 {
+        self.axes.get(name).map_or(0.0, |axis| axis.value(held))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_from_two_keys() {
+        assert_eq!(axis(false, true), 1.0);
+        assert_eq!(axis(true, false), -1.0);
+        assert_eq!(axis(true, true), 0.0);
+        assert_eq!(axis(false, false), 0.0);
+    }
+
+    #[test]
+    fn named_axis_reads_held_actions() {
+        let mut axes = AxisMap::default();
+        axes.bind("horizontal", "left", "right");
+
+        assert_eq!(axes.axis("horizontal", &["right", "jump"]), 1.0);
+        assert_eq!(axes.axis("horizontal", &["left", "right"]), 0.0);
+        assert_eq!(axes.axis::<&str>("horizontal", &[]), 0.0);
+        assert_eq!(axes.axis("vertical", &["left"]), 0.0);
+    }
+}
@@ -0,0 +1,52 @@
+use glam::Vec2;
+use specs::{Component, VecStorage};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2
+}
+
+impl Rect {
+    // Corners may come in any order, e.g. from a drag selection
+    pub fn from_corners(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b)
+        }
+    }
+}
+
+// Keeps an entity's translation inside the rect, e.g. a camera confined to the level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds(pub Rect);
+
+impl Component for Bounds {
+    type Storage = VecStorage<Self>;
+}
+
+impl Bounds {
+    pub fn clamp(&self, pos: Vec2) -> Vec2 {
+        pos.max(self.0.min).min(self.0.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level() -> Bounds {
+        Bounds(Rect::from_corners(Vec2::new(100.0, 50.0), Vec2::new(-100.0, -50.0)))
+    }
+
+    #[test]
+    fn position_outside_bounds_is_clamped_to_edge() {
+        assert_eq!(level().clamp(Vec2::new(250.0, 10.0)), Vec2::new(100.0, 10.0));
+        assert_eq!(level().clamp(Vec2::new(-300.0, -80.0)), Vec2::new(-100.0, -50.0));
+    }
+
+    #[test]
+    fn position_inside_bounds_is_untouched() {
+        assert_eq!(level().clamp(Vec2::new(20.0, -30.0)), Vec2::new(20.0, -30.0));
+    }
+}
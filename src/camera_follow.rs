@@ -0,0 +1,105 @@
+use glam::Vec2;
+
+use crate::view::ViewAdjust;
+
+// SpriteRenderScene steers the World's ViewAdjust with this in update; game code keeps `target` on the followed entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    // World position to keep in view
+    pub target: Vec2,
+    // Half extents about the view centre, in world units, that the target may wander without moving the camera
+    pub dead_zone: Vec2,
+    // Catch-up rate per second; anything not positive and finite snaps straight to the dead zone edge
+    pub smoothing: f32
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            target: Vec2::ZERO,
+            dead_zone: Vec2::new(64.0, 32.0),
+            smoothing: 8.0
+        }
+    }
+}
+
+impl CameraFollow {
+    // `width` and `height` are the same view size orthographic_projection is given
+    pub fn update(&self, view: ViewAdjust, width: f32, height: f32, dt: f32) -> ViewAdjust {
+        if !(dt.is_finite() && dt > 0.0) {
+            return view
+        }
+
+        let center = Vec2::new(width, height) / 2.0 + view.offset;
+        let dead_zone = self.dead_zone.abs();
+        let delta = self.target - center;
+        let overshoot = delta - delta.clamp(-dead_zone, dead_zone);
+
+        // Exponential approach so the catch-up takes the same wall time at any frame rate
+        let blend = if self.smoothing.is_finite() && self.smoothing > 0.0 {
+            1.0 - (-self.smoothing * dt).exp()
+        } else {
+            1.0
+        };
+
+        ViewAdjust {
+            offset: view.offset + overshoot * blend,
+            ..view
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn follow(target: Vec2) -> CameraFollow {
+        CameraFollow { target, ..Default::default() }
+    }
+
+    #[test]
+    fn target_inside_dead_zone_leaves_view_alone() {
+        let mut view = ViewAdjust::default();
+
+        for step in 0..60 {
+            let target = Vec2::new(480.0, 270.0) + Vec2::new(60.0, -30.0) * (step as f32 / 60.0);
+            view = follow(target).update(view, 960.0, 540.0, 1.0 / 60.0);
+        }
+
+        assert_eq!(view, ViewAdjust::default());
+    }
+
+    #[test]
+    fn target_outside_dead_zone_is_caught_up_smoothly() {
+        let camera = follow(Vec2::new(680.0, 270.0));
+        let mut view = ViewAdjust { zoom: 2.0, ..Default::default() };
+
+        let first = camera.update(view, 960.0, 540.0, 1.0 / 60.0);
+        assert!(first.offset.x > 0.0 && first.offset.x < 136.0);
+        assert_eq!(first.offset.y, 0.0);
+        assert_eq!(first.zoom, 2.0);
+
+        let mut previous = 0.0;
+        for _ in 0..120 {
+            view = camera.update(view, 960.0, 540.0, 1.0 / 60.0);
+            assert!(view.offset.x >= previous);
+            previous = view.offset.x;
+        }
+
+        // Settles with the target on the edge of the dead zone rather than dead centre
+        assert!((view.offset.x - 136.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn catch_up_follows_delta_time_not_frame_count() {
+        let camera = follow(Vec2::new(480.0, 570.0));
+
+        let mut fast = ViewAdjust::default();
+        for _ in 0..4 {
+            fast = camera.update(fast, 960.0, 540.0, 0.05);
+        }
+        let slow = camera.update(ViewAdjust::default(), 960.0, 540.0, 0.2);
+
+        assert!((fast.offset.y - slow.offset.y).abs() < 1e-3);
+    }
+}
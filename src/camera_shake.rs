@@ -0,0 +1,139 @@
+use glam::Vec2;
+
+use crate::view::ViewAdjust;
+
+// SpriteRenderScene decays this in update and adds it to the projection whenever one is in the World
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraShake {
+    trauma: f32,
+    // Trauma lost per second
+    pub decay: f32,
+    // World units at full trauma
+    pub max_offset: f32,
+    // Radians at full trauma
+    pub max_rotation: f32,
+    elapsed: f32
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: 16.0,
+            max_rotation: 0.05,
+            elapsed: 0.0
+        }
+    }
+}
+
+impl CameraShake {
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        if amount.is_finite() {
+            self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+        }
+    }
+
+    // `dt` is the frame delta in seconds, so the shake lasts the same wall time at any frame rate
+    pub fn update(&mut self, dt: f32) {
+        if !(dt.is_finite() && dt > 0.0) {
+            return
+        }
+
+        self.elapsed += dt;
+        self.trauma = (self.trauma - self.decay * dt).max(0.0);
+    }
+
+    pub fn offset(&self) -> Vec2 {
+        Vec2::new(noise(self.elapsed, 37.0, 1.3), noise(self.elapsed, 41.0, 2.9)) * self.max_offset * self.intensity()
+    }
+
+    pub fn rotation(&self) -> f32 {
+        noise(self.elapsed, 43.0, 0.7) * self.max_rotation * self.intensity()
+    }
+
+    pub fn apply(&self, view: ViewAdjust) -> ViewAdjust {
+        ViewAdjust {
+            offset: view.offset + self.offset(),
+            rotation: view.rotation + self.rotation(),
+            ..view
+        }
+    }
+
+    // Squaring makes small hits subtle and big hits violent
+    fn intensity(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+}
+
+// Cheap deterministic jitter in [-1, 1]; distinct frequencies and phases keep the axes out of step
+fn noise(time: f32, frequency: f32, phase: f32) -> f32 {
+    (time * frequency + phase).sin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trauma_produces_offset_that_decays_to_zero() {
+        let mut shake = CameraShake::default();
+        assert_eq!(shake.offset(), Vec2::ZERO);
+
+        shake.add_trauma(0.8);
+        shake.update(1.0 / 60.0);
+        assert!(shake.offset().length() > 0.0);
+        assert!(shake.rotation() != 0.0);
+
+        for _ in 0..60 {
+            shake.update(1.0 / 60.0);
+        }
+
+        assert_eq!(shake.trauma(), 0.0);
+        assert_eq!(shake.offset(), Vec2::ZERO);
+        assert_eq!(shake.rotation(), 0.0);
+    }
+
+    #[test]
+    fn decay_follows_delta_time_not_frame_count() {
+        let mut fast = CameraShake::default();
+        let mut slow = CameraShake::default();
+        fast.add_trauma(1.0);
+        slow.add_trauma(1.0);
+
+        for _ in 0..4 {
+            fast.update(0.05);
+        }
+        slow.update(0.2);
+
+        assert!((fast.trauma() - slow.trauma()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn trauma_is_clamped() {
+        let mut shake = CameraShake::default();
+
+        shake.add_trauma(3.0);
+        assert_eq!(shake.trauma(), 1.0);
+
+        shake.add_trauma(-5.0);
+        assert_eq!(shake.trauma(), 0.0);
+    }
+
+    #[test]
+    fn apply_adds_shake_to_view() {
+        let mut shake = CameraShake::default();
+        shake.add_trauma(1.0);
+        shake.update(0.1);
+
+        let view = shake.apply(ViewAdjust { zoom: 2.0, ..Default::default() });
+
+        assert_eq!(view.zoom, 2.0);
+        assert_eq!(view.offset, shake.offset());
+        assert_eq!(view.rotation, shake.rotation());
+    }
+}
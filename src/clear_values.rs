@@ -0,0 +1,118 @@
+use luminance_front::pipeline::PipelineState;
+use serde::{Deserialize, Deserializer};
+
+// None keeps PipelineState's default clear, Some(None) skips the clear, Some(Some(v)) clears to v
+pub type ClearOverride<V> = Option<Option<V>>;
+
+// A scene drawn as an overlay on top of another scene should return Some(None) from all three methods,
+// otherwise it wipes the color/depth/stencil the scene underneath already wrote this frame
+pub trait ClearValues {
+    fn clear_color(&self) -> ClearOverride<[f32; 4]> {
+        None
+    }
+
+    fn clear_depth(&self) -> ClearOverride<f32> {
+        None
+    }
+
+    fn clear_stencil(&self) -> ClearOverride<i32> {
+        None
+    }
+
+    fn apply_clear_values(&self, state: PipelineState) -> PipelineState {
+        let state = match self.clear_color() {
+            Some(clear_color) => state.set_clear_color(clear_color),
+            None => state
+        };
+
+        let state = match self.clear_depth() {
+            Some(clear_depth) => state.set_clear_depth(clear_depth),
+            None => state
+        };
+
+        match self.clear_stencil() {
+            Some(clear_stencil) => state.set_clear_stencil(clear_stencil),
+            None => state
+        }
+    }
+}
+
+// Use with #[serde(default, deserialize_with = "...")]: a missing field is None and an explicit null is Some(None)
+pub fn deserialize_clear_override<'de, D, V>(deserializer: D) -> Result<ClearOverride<V>, D::Error>
+    where D: Deserializer<'de>, V: Deserialize<'de> {
+    Option::<V>::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct SceneJSON {
+        #[serde(default, deserialize_with = "deserialize_clear_override")]
+        clear_stencil: ClearOverride<i32>
+    }
+
+    struct DefaultScene;
+
+    impl ClearValues for DefaultScene {}
+
+    struct StencilScene(i32);
+
+    impl ClearValues for StencilScene {
+        fn clear_stencil(&self) -> ClearOverride<i32> {
+            Some(Some(self.0))
+        }
+    }
+
+    #[test]
+    fn clear_override_distinguishes_missing_null_and_value() {
+        let missing: SceneJSON = serde_json::from_str("{}").unwrap();
+        let null: SceneJSON = serde_json::from_str(r#"{"clear_stencil": null}"#).unwrap();
+        let value: SceneJSON = serde_json::from_str(r#"{"clear_stencil": 1}"#).unwrap();
+
+        assert_eq!(missing.clear_stencil, None);
+        assert_eq!(null.clear_stencil, Some(None));
+        assert_eq!(value.clear_stencil, Some(Some(1)));
+    }
+
+    #[test]
+    fn default_clear_values_leave_pipeline_state_untouched() {
+        let state = DefaultScene.apply_clear_values(PipelineState::default());
+
+        assert_eq!(state.clear_depth(), PipelineState::default().clear_depth());
+        assert_eq!(state.clear_stencil(), PipelineState::default().clear_stencil());
+    }
+
+    struct OverlayScene;
+
+    impl ClearValues for OverlayScene {
+        fn clear_color(&self) -> ClearOverride<[f32; 4]> {
+            Some(None)
+        }
+
+        fn clear_depth(&self) -> ClearOverride<f32> {
+            Some(None)
+        }
+
+        fn clear_stencil(&self) -> ClearOverride<i32> {
+            Some(None)
+        }
+    }
+
+    #[test]
+    fn overlay_scene_skips_every_clear() {
+        let state = OverlayScene.apply_clear_values(PipelineState::default().set_clear_color([1.0, 0.0, 0.0, 1.0]));
+
+        assert!(state.clear_color().is_none());
+        assert_eq!(state.clear_depth(), None);
+        assert_eq!(state.clear_stencil(), None);
+    }
+
+    #[test]
+    fn scene_clear_stencil_is_applied_to_pipeline_state() {
+        let state = StencilScene(1).apply_clear_values(PipelineState::default());
+
+        assert_eq!(state.clear_stencil(), Some(1));
+    }
+}
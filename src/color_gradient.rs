@@ -0,0 +1,157 @@
+use std::convert::TryFrom;
+
+use glam::Vec4;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ColorKeyframe {
+    pub time: f32,
+    pub color: [f32; 4]
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ColorGradientJSON {
+    keyframes: Vec<ColorKeyframe>,
+    #[serde(default)]
+    repeat: bool
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "ColorGradientJSON")]
+pub struct ColorGradient {
+    keyframes: Vec<ColorKeyframe>,
+    repeat: bool
+}
+
+impl ColorGradient {
+    pub fn constant(color: [f32; 4]) -> Self {
+        Self {
+            keyframes: vec![ColorKeyframe { time: 0.0, color }],
+            repeat: false
+        }
+    }
+
+    pub fn evaluate(&self, time: f32) -> [f32; 4] {
+        let (first, last) = match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return [0.0, 0.0, 0.0, 1.0]
+        };
+
+        let time = if self.repeat && last.time > first.time {
+            first.time + (time - first.time).rem_euclid(last.time - first.time)
+        } else {
+            time
+        };
+
+        if time <= first.time {
+            return first.color
+        }
+
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+
+            if time <= to.time {
+                let t = (time - from.time) / (to.time - from.time);
+
+                return Vec4::from(from.color).lerp(Vec4::from(to.color), t).into()
+            }
+        }
+
+        last.color
+    }
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum ColorGradientError {
+    #[error("Color gradient keyframe time must be finite, got {0}")]
+    NonFiniteTime(f32)
+}
+
+impl TryFrom<ColorGradientJSON> for ColorGradient {
+    type Error = ColorGradientError;
+
+    fn try_from(json: ColorGradientJSON) -> Result<Self, Self::Error> {
+        let mut keyframes = json.keyframes;
+
+        if let Some(keyframe) = keyframes.iter().find(|keyframe| !keyframe.time.is_finite()) {
+            return Err(ColorGradientError::NonFiniteTime(keyframe.time))
+        }
+
+        // Every time is finite past the check above, so partial_cmp always has an answer
+        keyframes.sort_by(|a, b| a.time.partial_cmp(&b.time).expect("Failed to compare keyframe times"));
+
+        Ok(Self {
+            keyframes,
+            repeat: json.repeat
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+    const GREEN: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+    const BLUE: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+    fn gradient(repeat: bool) -> ColorGradient {
+        serde_json::from_str(&format!(
+            r#"{{
+                "keyframes": [
+                    {{ "time": 2.0, "color": [0.0, 0.0, 1.0, 1.0] }},
+                    {{ "time": 0.0, "color": [1.0, 0.0, 0.0, 1.0] }},
+                    {{ "time": 1.0, "color": [0.0, 1.0, 0.0, 1.0] }}
+                ],
+                "repeat": {}
+            }}"#,
+            repeat
+        )).unwrap()
+    }
+
+    fn assert_color_eq(actual: [f32; 4], expected: [f32; 4]) {
+        assert!(
+            Vec4::from(actual).abs_diff_eq(Vec4::from(expected), 1e-5),
+            "expected {:?}, got {:?}", expected, actual
+        );
+    }
+
+    #[test]
+    fn interpolates_between_keyframes() {
+        let gradient = gradient(false);
+
+        assert_color_eq(gradient.evaluate(0.5), [0.5, 0.5, 0.0, 1.0]);
+        assert_color_eq(gradient.evaluate(1.0), GREEN);
+        assert_color_eq(gradient.evaluate(1.25), [0.0, 0.75, 0.25, 1.0]);
+    }
+
+    #[test]
+    fn clamps_outside_keyframe_range() {
+        let gradient = gradient(false);
+
+        assert_color_eq(gradient.evaluate(-3.0), RED);
+        assert_color_eq(gradient.evaluate(10.0), BLUE);
+    }
+
+    #[test]
+    fn repeat_wraps_time_into_keyframe_range() {
+        let gradient = gradient(true);
+
+        assert_color_eq(gradient.evaluate(2.5), [0.5, 0.5, 0.0, 1.0]);
+        assert_color_eq(gradient.evaluate(-0.5), [0.0, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn rejects_non_finite_keyframe_times() {
+        let json = ColorGradientJSON {
+            keyframes: vec![
+                ColorKeyframe { time: 0.0, color: RED },
+                ColorKeyframe { time: f32::NAN, color: BLUE }
+            ],
+            repeat: false
+        };
+
+        assert!(matches!(ColorGradient::try_from(json), Err(ColorGradientError::NonFiniteTime(time)) if time.is_nan()));
+    }
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct ComboDefinition {
+    pub name: String,
+    pub steps: Vec<String>,
+    pub step_timeout: Duration
+}
+
+#[derive(Debug, Clone)]
+struct ComboProgress {
+    definition: ComboDefinition,
+    // fallback[i] is the length of the longest proper prefix of steps[..=i] that is also a suffix of it
+    fallback: Vec<usize>,
+    matched: usize,
+    last_step_at: Duration
+}
+
+impl ComboProgress {
+    fn new(definition: ComboDefinition) -> Self {
+        Self {
+            fallback: prefix_fallback(&definition.steps),
+            definition,
+            matched: 0,
+            last_step_at: Duration::default()
+        }
+    }
+
+    // Returns how many times the combo completed with this frame's presses
+    fn advance(&mut self, pressed: &[&str], now: Duration) -> usize {
+        let steps = &self.definition.steps;
+
+        // Actions outside the combo (e.g. kick during a throw) never break it
+        let mut remaining: Vec<&str> = pressed.iter()
+            .copied()
+            .filter(|action| steps.iter().any(|step| step == action))
+            .collect();
+
+        if steps.is_empty() || remaining.is_empty() {
+            return 0
+        }
+
+        if self.matched > 0 && now.saturating_sub(self.last_step_at) > self.definition.step_timeout {
+            self.matched = 0;
+        }
+
+        let mut completed = 0;
+        loop {
+            // Presses within one frame are unordered, so any of them may be the next step
+            if let Some(i) = remaining.iter().position(|&action| action == steps[self.matched]) {
+                remaining.swap_remove(i);
+                self.matched += 1;
+                self.last_step_at = now;
+
+                if self.matched == steps.len() {
+                    self.matched = 0;
+                    completed += 1;
+                }
+            } else if remaining.is_empty() {
+                break
+            } else if self.matched == 0 {
+                // None of the leftover presses can start the combo either
+                break
+            } else {
+                // Step back to the longest prefix that is still matched, e.g. down, down, down keeps "down, down"
+                self.matched = self.fallback[self.matched - 1];
+            }
+        }
+
+        completed
+    }
+}
+
+fn prefix_fallback(steps: &[String]) -> Vec<usize> {
+    let mut fallback = vec![0; steps.len()];
+    let mut length = 0;
+
+    for i in 1..steps.len() {
+        while length > 0 && steps[i] != steps[length] {
+            length = fallback[length - 1];
+        }
+
+        if steps[i] == steps[length] {
+            length += 1;
+        }
+
+        fallback[i] = length;
+    }
+
+    fallback
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ComboDetector {
+    combos: Vec<ComboProgress>,
+    fired: Vec<String>
+}
+
+impl ComboDetector {
+    pub fn new(definitions: Vec<ComboDefinition>) -> Self {
+        Self {
+            combos: definitions.into_iter()
+                .map(ComboProgress::new)
+                .collect(),
+            fired: Vec::new()
+        }
+    }
+
+    // `pressed` holds the actions newly pressed this frame, in any order; `now` is the monotonic game time
+    pub fn update<S: AsRef<str>>(&mut self, pressed: &[S], now: Duration) {
+        self.fired.clear();
+
+        let pressed: Vec<&str> = pressed.iter().map(AsRef::as_ref).collect();
+        for combo in &mut self.combos {
+            if combo.advance(&pressed, now) > 0 {
+                self.fired.push(combo.definition.name.clone());
+            }
+        }
+    }
+
+    pub fn fired(&self, name: &str) -> bool {
+        self.fired.iter().any(|fired| fired == name)
+    }
+
+    pub fn fired_combos(&self) -> &[String] {
+        &self.fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(name: &str, steps: &[&str]) -> ComboDetector {
+        ComboDetector::new(vec![ComboDefinition {
+            name: String::from(name),
+            steps: steps.iter().map(|step| step.to_string()).collect(),
+            step_timeout: Duration::from_millis(200)
+        }])
+    }
+
+    fn hadouken() -> ComboDetector {
+        combo("hadouken", &["down", "down_forward", "forward", "punch"])
+    }
+
+    #[test]
+    fn sequence_within_window_fires() {
+        let mut detector = hadouken();
+
+        detector.update(&["down"], Duration::from_millis(0));
+        detector.update(&["down_forward"], Duration::from_millis(100));
+        detector.update(&["forward"], Duration::from_millis(200));
+        assert!(!detector.fired("hadouken"));
+
+        detector.update(&["punch"], Duration::from_millis(300));
+        assert!(detector.fired("hadouken"));
+
+        detector.update::<&str>(&[], Duration::from_millis(316));
+        assert!(!detector.fired("hadouken"));
+    }
+
+    #[test]
+    fn sequence_too_slow_does_not_fire() {
+        let mut detector = hadouken();
+
+        detector.update(&["down"], Duration::from_millis(0));
+        detector.update(&["down_forward"], Duration::from_millis(100));
+        detector.update(&["forward"], Duration::from_millis(500));
+        detector.update(&["punch"], Duration::from_millis(600));
+
+        assert!(!detector.fired("hadouken"));
+    }
+
+    #[test]
+    fn several_steps_in_one_frame_complete_the_combo() {
+        let mut detector = hadouken();
+
+        detector.update(&["down", "down_forward", "forward", "punch"], Duration::from_millis(0));
+
+        assert!(detector.fired("hadouken"));
+    }
+
+    #[test]
+    fn actions_outside_the_combo_are_ignored() {
+        let mut detector = combo("uppercut", &["down", "forward", "punch"]);
+
+        detector.update(&["down"], Duration::from_millis(0));
+        detector.update(&["forward", "kick"], Duration::from_millis(50));
+        detector.update(&["jump"], Duration::from_millis(100));
+        detector.update(&["punch"], Duration::from_millis(150));
+
+        assert!(detector.fired("uppercut"));
+    }
+
+    #[test]
+    fn repeated_first_step_falls_back_to_matched_prefix() {
+        let mut detector = combo("dash", &["down", "down", "forward"]);
+
+        for (i, action) in ["down", "down", "down", "forward"].iter().enumerate() {
+            detector.update(&[*action], Duration::from_millis(50 * i as u64));
+        }
+
+        assert!(detector.fired("dash"));
+    }
+
+    #[test]
+    fn presses_in_one_frame_match_in_any_order() {
+        let mut detector = combo("uppercut", &["down", "forward", "punch"]);
+
+        detector.update(&["down"], Duration::from_millis(0));
+        detector.update(&["punch", "forward"], Duration::from_millis(50));
+
+        assert!(detector.fired("uppercut"));
+    }
+
+    #[test]
+    fn wrong_combo_step_resets_progress() {
+        let mut detector = combo("uppercut", &["down", "forward", "punch"]);
+
+        detector.update(&["down"], Duration::from_millis(0));
+        detector.update(&["punch"], Duration::from_millis(50));
+        detector.update(&["forward"], Duration::from_millis(100));
+        detector.update(&["punch"], Duration::from_millis(150));
+
+        assert!(!detector.fired("uppercut"));
+    }
+}
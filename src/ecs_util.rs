@@ -0,0 +1,44 @@
+use specs::{Component, Join, World, WorldExt};
+
+// Deletions are deferred like any Entities::delete, so the entities stay readable until the next World::maintain
+pub fn despawn_all_with<C: Component>(ecs: &World) -> usize {
+    let entities = ecs.entities();
+    let storage = ecs.read_storage::<C>();
+
+    let mut despawned = 0;
+    for (entity, _) in (&entities, &storage).join() {
+        entities.delete(entity).expect("Failed to delete entity");
+        despawned += 1;
+    }
+
+    despawned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, NullStorage};
+
+    #[derive(Default)]
+    struct Projectile;
+
+    impl Component for Projectile {
+        type Storage = NullStorage<Self>;
+    }
+
+    #[test]
+    fn despawns_only_entities_with_component() {
+        let mut ecs = World::new();
+        ecs.register::<Projectile>();
+
+        let projectiles: Vec<_> = (0..3).map(|_| ecs.create_entity().with(Projectile).build()).collect();
+        let others: Vec<_> = (0..2).map(|_| ecs.create_entity().build()).collect();
+
+        assert_eq!(despawn_all_with::<Projectile>(&ecs), 3);
+        ecs.maintain();
+
+        assert!(projectiles.iter().all(|&entity| !ecs.is_alive(entity)));
+        assert!(others.iter().all(|&entity| ecs.is_alive(entity)));
+        assert_eq!(ecs.read_storage::<Projectile>().join().count(), 0);
+    }
+}
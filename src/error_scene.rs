@@ -0,0 +1,222 @@
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+
+use anyhow::{Error, Result};
+use game_engine::input::Input;
+use game_engine::scenes::Scene;
+use game_engine::scenes::scene_stack::SceneTransition;
+use luminance_front::context::GraphicsContext;
+use luminance_front::pipeline::{PipelineError, PipelineState};
+use luminance_front::texture::Dim2;
+use luminance_glfw::GL33Context;
+use specs::World;
+
+const ERROR_CLEAR_COLOR: [f32; 4] = [0.4, 0.0, 0.0, 1.0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryPolicy {
+    // Swap the failing scene for an ErrorScene and keep the game running
+    Recover,
+    // Re-panic / return the error so problems surface immediately during development
+    Strict
+}
+
+impl Default for BoundaryPolicy {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            BoundaryPolicy::Strict
+        } else {
+            BoundaryPolicy::Recover
+        }
+    }
+}
+
+pub struct ErrorScene<T: Input + Debug> {
+    scene_name: String,
+    message: String,
+    phantom_input: PhantomData<T>
+}
+
+impl<T: Input + Debug> ErrorScene<T> {
+    pub fn new(scene_name: String, message: String) -> Self {
+        Self {
+            scene_name,
+            message,
+            phantom_input: Default::default()
+        }
+    }
+}
+
+impl<T: Input + Debug> Debug for ErrorScene<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorScene")
+            .field("scene_name", &self.scene_name)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl<T: Input + Debug> Scene<T> for ErrorScene<T> {
+    fn update(&mut self, _ecs: &mut World) -> Result<SceneTransition<T>> {
+        Ok(SceneTransition::NONE)
+    }
+
+    fn draw(&mut self, _ecs: &mut World, context: &mut GL33Context) -> Result<()> {
+        let back_buffer = context.back_buffer()
+            .expect("Failed to get back buffer");
+
+        context.new_pipeline_gate()
+            .pipeline::<PipelineError, Dim2, (), (), _>(
+                &back_buffer,
+                &PipelineState::default().set_clear_color(ERROR_CLEAR_COLOR),
+                |_, _| Ok(())
+            )
+            .into_result()
+            .map_err(Error::new)
+    }
+
+    fn interact(&mut self, _ecs: &mut World, _input: &T) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_name(&self) -> String {
+        format!("Error in {}: {}", self.scene_name, self.message)
+    }
+
+    fn is_finished(&self, _ecs: &mut World) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+pub struct ErrorBoundary<T: 'static + Input + Debug> {
+    scene: Box<dyn Scene<T>>,
+    policy: BoundaryPolicy,
+    failed: bool
+}
+
+impl<T: 'static + Input + Debug> ErrorBoundary<T> {
+    pub fn new(scene: Box<dyn Scene<T>>, policy: BoundaryPolicy) -> Self {
+        Self {
+            scene,
+            policy,
+            failed: false
+        }
+    }
+
+    fn guard<R>(&mut self, phase: &str, fallback: R, call: impl FnOnce(&mut dyn Scene<T>) -> Result<R>) -> Result<R> {
+        let scene_name = self.scene.get_name();
+        let scene = &mut *self.scene;
+
+        let message = match panic::catch_unwind(AssertUnwindSafe(|| call(scene))) {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(e)) if self.policy == BoundaryPolicy::Strict => return Err(e),
+            Err(payload) if self.policy == BoundaryPolicy::Strict => panic::resume_unwind(payload),
+            Ok(Err(e)) => format!("{:#}", e),
+            Err(payload) => panic_message(payload.as_ref())
+        };
+
+        tracing::error!(scene = %scene_name, phase, error = %message, "Scene failed, switching to error scene");
+
+        self.scene = Box::new(ErrorScene::<T>::new(scene_name, message));
+        self.failed = true;
+
+        Ok(fallback)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Scene panicked with a non-string payload")
+    }
+}
+
+impl<T: 'static + Input + Debug> Debug for ErrorBoundary<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorBoundary")
+            .field("scene", &self.scene.get_name())
+            .field("policy", &self.policy)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+impl<T: 'static + Input + Debug> Scene<T> for ErrorBoundary<T> {
+    fn update(&mut self, ecs: &mut World) -> Result<SceneTransition<T>> {
+        self.guard("update", SceneTransition::NONE, |scene| scene.update(ecs))
+    }
+
+    fn draw(&mut self, ecs: &mut World, context: &mut GL33Context) -> Result<()> {
+        self.guard("draw", (), |scene| scene.draw(ecs, context))
+    }
+
+    fn interact(&mut self, ecs: &mut World, input: &T) -> Result<()> {
+        self.guard("interact", (), |scene| scene.interact(ecs, input))
+    }
+
+    fn get_name(&self) -> String {
+        self.scene.get_name()
+    }
+
+    fn is_finished(&self, ecs: &mut World) -> Result<bool> {
+        self.scene.is_finished(ecs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_engine::input::multi_input::MultiInput;
+
+    #[derive(Debug)]
+    struct PanickingScene;
+
+    impl Scene<MultiInput> for PanickingScene {
+        fn update(&mut self, _ecs: &mut World) -> Result<SceneTransition<MultiInput>> {
+            panic!("update exploded")
+        }
+
+        fn draw(&mut self, _ecs: &mut World, _context: &mut GL33Context) -> Result<()> {
+            Ok(())
+        }
+
+        fn interact(&mut self, _ecs: &mut World, _input: &MultiInput) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_name(&self) -> String {
+            String::from("Panicking Scene")
+        }
+
+        fn is_finished(&self, _ecs: &mut World) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn recover_policy_swaps_in_error_scene_after_panic() {
+        let mut ecs = World::new();
+        let mut boundary = ErrorBoundary::new(Box::new(PanickingScene), BoundaryPolicy::Recover);
+
+        assert!(boundary.update(&mut ecs).is_ok());
+        assert!(boundary.failed);
+        assert_eq!(boundary.get_name(), "Error in Panicking Scene: update exploded");
+
+        // The error scene is now active, so further updates succeed without panicking
+        assert!(boundary.update(&mut ecs).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "update exploded")]
+    fn strict_policy_re_panics() {
+        let mut ecs = World::new();
+        let mut boundary = ErrorBoundary::new(Box::new(PanickingScene), BoundaryPolicy::Strict);
+
+        let _ = boundary.update(&mut ecs);
+    }
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FitMode {
+    Stretch,
+    // Keeps the design aspect ratio, leaving bars at the top/bottom or left/right
+    Letterbox
+}
+
+impl Default for FitMode {
+    fn default() -> Self {
+        FitMode::Stretch
+    }
+}
+
+impl FitMode {
+    pub fn content_rect(self, framebuffer_size: [u32; 2], design_size: [f32; 2]) -> FitRect {
+        match self {
+            FitMode::Stretch => FitRect::whole(framebuffer_size),
+            FitMode::Letterbox => fit_viewport(framebuffer_size, design_size)
+        }
+    }
+}
+
+// Pixel rect with its origin at the bottom left, matching GL viewport and scissor coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FitRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32
+}
+
+impl FitRect {
+    pub fn whole([width, height]: [u32; 2]) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width,
+            height
+        }
+    }
+}
+
+// Largest centred rect inside the framebuffer with the design aspect ratio
+pub fn fit_viewport([width, height]: [u32; 2], [design_width, design_height]: [f32; 2]) -> FitRect {
+    if !(design_width > 0.0 && design_height > 0.0) {
+        return FitRect::whole([width, height])
+    }
+
+    let scale = (width as f32 / design_width).min(height as f32 / design_height);
+    let fitted_width = ((design_width * scale).round() as u32).min(width);
+    let fitted_height = ((design_height * scale).round() as u32).min(height);
+
+    FitRect {
+        x: (width - fitted_width) / 2,
+        y: (height - fitted_height) / 2,
+        width: fitted_width,
+        height: fitted_height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESIGN: [f32; 2] = [960.0, 540.0];
+
+    fn aspect(rect: FitRect) -> f32 {
+        rect.width as f32 / rect.height as f32
+    }
+
+    #[test]
+    fn four_by_three_window_is_letterboxed() {
+        let rect = fit_viewport([1024, 768], DESIGN);
+
+        assert_eq!(rect, FitRect { x: 0, y: 96, width: 1024, height: 576 });
+    }
+
+    #[test]
+    fn wide_window_is_pillarboxed_at_design_aspect() {
+        let rect = fit_viewport([2560, 1080], DESIGN);
+
+        assert_eq!(rect, FitRect { x: 320, y: 0, width: 1920, height: 1080 });
+        assert!((aspect(rect) - 16.0 / 9.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stretch_uses_whole_framebuffer() {
+        assert_eq!(FitMode::default(), FitMode::Stretch);
+        assert_eq!(FitMode::Stretch.content_rect([1024, 768], DESIGN), FitRect::whole([1024, 768]));
+    }
+
+    #[test]
+    fn degenerate_sizes_do_not_panic() {
+        assert_eq!(fit_viewport([0, 0], DESIGN), FitRect::whole([0, 0]));
+        assert_eq!(fit_viewport([1024, 768], [0.0, 540.0]), FitRect::whole([1024, 768]));
+    }
+}
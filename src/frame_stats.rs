@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+// Longest window worth reporting; also keeps Duration::from_secs_f32 far away from overflowing
+const MAX_REPORT_INTERVAL_SECS: f32 = 24.0 * 60.0 * 60.0;
+const MAX_FRAME_BUDGET_MS: f32 = 60.0 * 1000.0;
+
+// 4ms buckets; the last one also collects every frame slower than 60ms
+pub const FRAME_TIME_BUCKETS: usize = 16;
+pub const FRAME_TIME_BUCKET_MS: u32 = 4;
+// Frames the recorder's histogram looks back over, independent of the report window
+const FRAME_HISTORY_LEN: usize = 1000;
+
+// Inserted into the World by the stress test scene each time a report window closes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    pub frames: u32,
+    pub fps: f32,
+    pub avg_draw_ms: f32,
+    // Rolling over the recorder's frame history rather than just this window, so a spike stays visible for a while
+    pub frame_time_buckets: [u32; FRAME_TIME_BUCKETS],
+    pub one_percent_low_fps: f32
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum FrameStatsError {
+    #[error("Report interval must be a positive number of seconds no longer than a day, got {0}")]
+    InvalidReportInterval(f32),
+    #[error("Frame budget must be a positive number of milliseconds no longer than a minute, got {0}")]
+    InvalidFrameBudget(f32)
+}
+
+// Duration::from_secs_f32 panics on negative, NaN or overflowing input, so config values go through here first
+pub fn report_interval_from_secs(secs: f32) -> Result<Duration, FrameStatsError> {
+    if !(secs.is_finite() && secs > 0.0 && secs <= MAX_REPORT_INTERVAL_SECS) {
+        return Err(FrameStatsError::InvalidReportInterval(secs))
+    }
+
+    Ok(Duration::from_secs_f32(secs))
+}
+
+pub fn frame_budget_from_millis(millis: f32) -> Result<Duration, FrameStatsError> {
+    if !(millis.is_finite() && millis > 0.0 && millis <= MAX_FRAME_BUDGET_MS) {
+        return Err(FrameStatsError::InvalidFrameBudget(millis))
+    }
+
+    Ok(Duration::from_secs_f32(millis / 1000.0))
+}
+
+// Bucket counts over the last `capacity` frame times, kept in step as old frames roll out
+#[derive(Debug, Clone)]
+pub struct FrameTimeHistogram {
+    capacity: usize,
+    frame_times: VecDeque<Duration>,
+    buckets: [u32; FRAME_TIME_BUCKETS]
+}
+
+impl FrameTimeHistogram {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            frame_times: VecDeque::with_capacity(capacity.max(1)),
+            buckets: [0; FRAME_TIME_BUCKETS]
+        }
+    }
+
+    pub fn record(&mut self, frame_time: Duration) {
+        if self.frame_times.len() == self.capacity {
+            if let Some(oldest) = self.frame_times.pop_front() {
+                self.buckets[bucket_index(oldest)] -= 1;
+            }
+        }
+
+        self.frame_times.push_back(frame_time);
+        self.buckets[bucket_index(frame_time)] += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.frame_times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frame_times.is_empty()
+    }
+
+    pub fn buckets(&self) -> [u32; FRAME_TIME_BUCKETS] {
+        self.buckets
+    }
+
+    // Fps over the slowest 1% of the history (at least one frame); 0 until a frame is recorded
+    pub fn one_percent_low_fps(&self) -> f32 {
+        let mut frame_times: Vec<Duration> = self.frame_times.iter().copied().collect();
+        frame_times.sort_unstable_by(|a, b| b.cmp(a));
+
+        let count = frame_times.len().div_ceil(100).max(1);
+        let slowest: Duration = frame_times.iter().take(count).sum();
+        if slowest == Duration::default() {
+            return 0.0
+        }
+
+        count as f32 / slowest.as_secs_f32()
+    }
+}
+
+fn bucket_index(frame_time: Duration) -> usize {
+    let index = frame_time.as_micros() / (FRAME_TIME_BUCKET_MS as u128 * 1000);
+
+    (index as usize).min(FRAME_TIME_BUCKETS - 1)
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameStatsRecorder {
+    report_interval: Duration,
+    frames: u32,
+    draw_time: Duration,
+    window_start: Option<Instant>,
+    last_frame: Option<Instant>,
+    histogram: FrameTimeHistogram
+}
+
+impl FrameStatsRecorder {
+    pub fn new(report_interval: Duration) -> Self {
+        Self {
+            report_interval,
+            frames: 0,
+            draw_time: Duration::default(),
+            window_start: None,
+            last_frame: None,
+            histogram: FrameTimeHistogram::new(FRAME_HISTORY_LEN)
+        }
+    }
+
+    pub fn histogram(&self) -> &FrameTimeHistogram {
+        &self.histogram
+    }
+
+    // Returns the stats for the window once report_interval has passed since its first frame
+    pub fn record_frame(&mut self, draw_time: Duration, now: Instant) -> Option<FrameStats> {
+        if let Some(last_frame) = self.last_frame.replace(now) {
+            self.histogram.record(now.saturating_duration_since(last_frame));
+        }
+
+        // The very first frame only opens a window; each later frame closes one frame interval
+        let window_start = match self.window_start {
+            Some(window_start) => window_start,
+            None => {
+                self.window_start = Some(now);
+                return None
+            }
+        };
+
+        self.frames += 1;
+        self.draw_time += draw_time;
+
+        let elapsed = now.saturating_duration_since(window_start);
+        if elapsed < self.report_interval || elapsed == Duration::default() {
+            return None
+        }
+
+        let stats = FrameStats {
+            frames: self.frames,
+            fps: self.frames as f32 / elapsed.as_secs_f32(),
+            avg_draw_ms: self.draw_time.as_secs_f32() * 1000.0 / self.frames as f32,
+            frame_time_buckets: self.histogram.buckets(),
+            one_percent_low_fps: self.histogram.one_percent_low_fps()
+        };
+
+        self.frames = 0;
+        self.draw_time = Duration::default();
+        self.window_start = Some(now);
+
+        Some(stats)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameBudget {
+    budget: Duration,
+    warn_interval: Duration,
+    last_warning: Option<Instant>
+}
+
+impl FrameBudget {
+    pub fn new(budget: Duration, warn_interval: Duration) -> Self {
+        Self {
+            budget,
+            warn_interval,
+            last_warning: None
+        }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    // True when the frame overran and no warning was raised within the last warn_interval, so a hitchy stretch logs once
+    pub fn check(&mut self, frame_time: Duration, now: Instant) -> bool {
+        if frame_time <= self.budget {
+            return false
+        }
+
+        if let Some(last_warning) = self.last_warning {
+            if now.saturating_duration_since(last_warning) < self.warn_interval {
+                return false
+            }
+        }
+
+        self.last_warning = Some(now);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_report_intervals_that_would_panic() {
+        assert!(report_interval_from_secs(-1.0).is_err());
+        assert!(report_interval_from_secs(0.0).is_err());
+        assert!(report_interval_from_secs(f32::NAN).is_err());
+        assert!(report_interval_from_secs(f32::INFINITY).is_err());
+        assert!(report_interval_from_secs(f32::MAX).is_err());
+
+        assert_eq!(report_interval_from_secs(0.5), Ok(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn reports_nonzero_stats_once_interval_passes() {
+        let mut recorder = FrameStatsRecorder::new(Duration::from_millis(50));
+        let start = Instant::now();
+        let draw_time = Duration::from_millis(2);
+
+        let reports: Vec<FrameStats> = (0..=10)
+            .filter_map(|frame| recorder.record_frame(draw_time, start + Duration::from_millis(10 * frame)))
+            .collect();
+
+        // A steady 10ms stream is 100 fps in every window; the frame closing one window opens the next
+        assert_eq!(reports.len(), 2);
+        for report in &reports {
+            assert_eq!(report.frames, 5);
+            assert!((report.fps - 100.0).abs() < 1e-3);
+            assert!((report.avg_draw_ms - 2.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn rejects_frame_budgets_out_of_range() {
+        assert!(frame_budget_from_millis(0.0).is_err());
+        assert!(frame_budget_from_millis(f32::NAN).is_err());
+        assert!(frame_budget_from_millis(120_000.0).is_err());
+
+        assert_eq!(frame_budget_from_millis(250.0), Ok(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn slow_frames_warn_with_throttling() {
+        let mut budget = FrameBudget::new(Duration::from_millis(16), Duration::from_secs(1));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        assert!(!budget.check(Duration::from_millis(10), at(0)));
+        assert!(budget.check(Duration::from_millis(40), at(100)));
+
+        // Still overrunning, but within a second of the last warning
+        assert!(!budget.check(Duration::from_millis(40), at(500)));
+        assert!(!budget.check(Duration::from_millis(10), at(1200)));
+
+        assert!(budget.check(Duration::from_millis(40), at(1300)));
+    }
+
+    #[test]
+    fn histogram_buckets_spikes_and_rolls_them_out() {
+        let mut histogram = FrameTimeHistogram::new(100);
+        for _ in 0..99 {
+            histogram.record(Duration::from_millis(16));
+        }
+        histogram.record(Duration::from_millis(100));
+
+        let buckets = histogram.buckets();
+        assert_eq!(buckets[4], 99);
+        assert_eq!(buckets[FRAME_TIME_BUCKETS - 1], 1);
+        assert_eq!(buckets.iter().sum::<u32>(), 100);
+        // The one 100ms frame is the slowest 1% on its own
+        assert!((histogram.one_percent_low_fps() - 10.0).abs() < 1e-3);
+
+        for _ in 0..100 {
+            histogram.record(Duration::from_millis(9));
+        }
+
+        let buckets = histogram.buckets();
+        assert_eq!(histogram.len(), 100);
+        assert_eq!(buckets[2], 100);
+        assert_eq!(buckets[FRAME_TIME_BUCKETS - 1], 0);
+        assert!((histogram.one_percent_low_fps() - 1000.0 / 9.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn recorder_reports_histogram_of_frame_intervals() {
+        let mut recorder = FrameStatsRecorder::new(Duration::from_millis(100));
+        let start = Instant::now();
+        let times = [0, 10, 20, 30, 100];
+
+        let report = times.iter()
+            .filter_map(|&millis| recorder.record_frame(Duration::from_millis(1), start + Duration::from_millis(millis)))
+            .last()
+            .unwrap();
+
+        // Three steady 10ms intervals then one 70ms hitch
+        assert_eq!(report.frame_time_buckets[2], 3);
+        assert_eq!(report.frame_time_buckets[FRAME_TIME_BUCKETS - 1], 1);
+        assert!((report.one_percent_low_fps - 1000.0 / 70.0).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,66 @@
+use glam::Vec2;
+
+// A cell that is not a positive finite size leaves the position untouched instead of producing NaN
+pub fn snap_to_grid(pos: Vec2, cell: f32) -> Vec2 {
+    if !(cell.is_finite() && cell > 0.0) {
+        return pos
+    }
+
+    (pos / cell).round() * cell
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSnap {
+    pub enabled: bool,
+    pub cell: f32
+}
+
+impl Default for GridSnap {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cell: 16.0
+        }
+    }
+}
+
+impl GridSnap {
+    pub fn apply(&self, pos: Vec2) -> Vec2 {
+        if self.enabled {
+            snap_to_grid(pos, self.cell)
+        } else {
+            pos
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_nearest_cell() {
+        assert_eq!(snap_to_grid(Vec2::new(10.4, 19.9), 10.0), Vec2::new(10.0, 20.0));
+        assert_eq!(snap_to_grid(Vec2::new(-14.0, 4.0), 10.0), Vec2::new(-10.0, 0.0));
+    }
+
+    #[test]
+    fn invalid_cell_leaves_position_untouched() {
+        let pos = Vec2::new(10.4, 19.9);
+
+        assert_eq!(snap_to_grid(pos, 0.0), pos);
+        assert_eq!(snap_to_grid(pos, -10.0), pos);
+        assert_eq!(snap_to_grid(pos, f32::NAN), pos);
+    }
+
+    #[test]
+    fn grid_snap_only_applies_when_enabled() {
+        let pos = Vec2::new(10.4, 19.9);
+        let mut snap = GridSnap { enabled: false, cell: 10.0 };
+
+        assert_eq!(snap.apply(pos), pos);
+
+        snap.enabled = true;
+        assert_eq!(snap.apply(pos), Vec2::new(10.0, 20.0));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RepeatConfig {
+    pub initial_delay: Duration,
+    pub interval: Duration
+}
+
+impl Default for RepeatConfig {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(400),
+            interval: Duration::from_millis(100)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HoldState {
+    next_repeat: Duration,
+    repeating: bool
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActionRepeat {
+    config: RepeatConfig,
+    held: HashMap<String, HoldState>
+}
+
+impl ActionRepeat {
+    pub fn new(config: RepeatConfig) -> Self {
+        Self {
+            config,
+            held: HashMap::new()
+        }
+    }
+
+    // `held` holds every action currently down, `now` is the monotonic game time; anything missing counts as released
+    pub fn update<S: AsRef<str>>(&mut self, held: &[S], now: Duration) {
+        self.held.retain(|action, _| held.iter().any(|h| h.as_ref() == action));
+
+        for action in held {
+            let config = self.config;
+            let state = match self.held.entry(action.as_ref().to_string()) {
+                // The press itself never counts as a repeat, even with no initial delay
+                Entry::Vacant(entry) => {
+                    entry.insert(HoldState {
+                        next_repeat: now + config.initial_delay,
+                        repeating: false
+                    });
+                    continue
+                },
+                Entry::Occupied(entry) => entry.into_mut()
+            };
+
+            state.repeating = now >= state.next_repeat;
+            if !state.repeating {
+                continue
+            }
+
+            if config.interval == Duration::default() {
+                state.next_repeat = now;
+                continue
+            }
+
+            // A long frame fires a single repeat rather than a burst of them
+            while state.next_repeat <= now {
+                state.next_repeat += config.interval;
+            }
+        }
+    }
+
+    // True only on the frames a repeat fires, never on the initial press
+    pub fn is_action_repeating(&self, action: &str) -> bool {
+        self.held.get(action).is_some_and(|state| state.repeating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repeat() -> ActionRepeat {
+        ActionRepeat::new(RepeatConfig {
+            initial_delay: Duration::from_millis(300),
+            interval: Duration::from_millis(100)
+        })
+    }
+
+    fn repeating_at(repeat: &mut ActionRepeat, millis: u64) -> bool {
+        repeat.update(&["down"], Duration::from_millis(millis));
+        repeat.is_action_repeating("down")
+    }
+
+    #[test]
+    fn held_action_repeats_after_delay_then_at_interval() {
+        let mut repeat = repeat();
+
+        let fired: Vec<u64> = (0..=700).step_by(50)
+            .filter(|&millis| repeating_at(&mut repeat, millis))
+            .collect();
+
+        assert_eq!(fired, vec![300, 400, 500, 600, 700]);
+    }
+
+    #[test]
+    fn releasing_restarts_the_initial_delay() {
+        let mut repeat = repeat();
+
+        assert!(!repeating_at(&mut repeat, 0));
+        assert!(repeating_at(&mut repeat, 300));
+
+        repeat.update::<&str>(&[], Duration::from_millis(350));
+        assert!(!repeat.is_action_repeating("down"));
+
+        assert!(!repeating_at(&mut repeat, 400));
+        assert!(!repeating_at(&mut repeat, 650));
+        assert!(repeating_at(&mut repeat, 700));
+    }
+
+    #[test]
+    fn long_frame_fires_one_repeat() {
+        let mut repeat = repeat();
+
+        assert!(!repeating_at(&mut repeat, 0));
+        assert!(repeating_at(&mut repeat, 1000));
+        assert!(!repeating_at(&mut repeat, 1050));
+        assert!(repeating_at(&mut repeat, 1100));
+    }
+
+    #[test]
+    fn zero_delay_still_skips_the_initial_press() {
+        let mut repeat = ActionRepeat::new(RepeatConfig {
+            initial_delay: Duration::default(),
+            interval: Duration::from_millis(100)
+        });
+
+        assert!(!repeating_at(&mut repeat, 0));
+        assert!(repeating_at(&mut repeat, 16));
+        assert!(!repeating_at(&mut repeat, 32));
+        assert!(repeating_at(&mut repeat, 116));
+    }
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const MAX_ACTIONS: usize = 64;
+const AXIS_SCALE: f32 = i16::MAX as f32;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InputSnapshotError {
+    #[error("An input layout holds at most 64 actions, got {0}")]
+    TooManyActions(usize),
+    #[error("Input snapshot bytes must be 8 plus 2 per axis, got {0}")]
+    InvalidLength(usize)
+}
+
+// Both peers agree on this order up front, so a snapshot only carries bits and numbers, never names
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InputLayout {
+    actions: Vec<String>,
+    axes: Vec<String>
+}
+
+impl InputLayout {
+    pub fn new(actions: &[&str], axes: &[&str]) -> Result<Self, InputSnapshotError> {
+        if actions.len() > MAX_ACTIONS {
+            return Err(InputSnapshotError::TooManyActions(actions.len()))
+        }
+
+        Ok(Self {
+            actions: actions.iter().map(|action| action.to_string()).collect(),
+            axes: axes.iter().map(|axis| axis.to_string()).collect()
+        })
+    }
+
+    // `held` holds the actions down this tick, `axis` reads the current value of a named axis in [-1, 1]
+    pub fn capture<S: AsRef<str>>(&self, held: &[S], axis: impl Fn(&str) -> f32) -> InputSnapshot {
+        let actions = self.actions.iter()
+            .enumerate()
+            .filter(|(_, action)| held.iter().any(|h| h.as_ref() == action.as_str()))
+            .fold(0, |bits, (i, _)| bits | 1 << i);
+
+        InputSnapshot {
+            actions,
+            axes: self.axes.iter().map(|name| quantize(axis(name))).collect()
+        }
+    }
+
+    pub fn playback(&self, snapshot: InputSnapshot) -> PlaybackInput<'_> {
+        PlaybackInput {
+            layout: self,
+            snapshot
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputSnapshot {
+    actions: u64,
+    axes: Vec<i16>
+}
+
+impl InputSnapshot {
+    // Little endian action bits followed by one i16 per axis
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + 2 * self.axes.len());
+        bytes.extend_from_slice(&self.actions.to_le_bytes());
+        for axis in &self.axes {
+            bytes.extend_from_slice(&axis.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InputSnapshotError> {
+        if bytes.len() < 8 || !(bytes.len() - 8).is_multiple_of(2) {
+            return Err(InputSnapshotError::InvalidLength(bytes.len()))
+        }
+
+        let (actions, axes) = bytes.split_at(8);
+        let mut action_bytes = [0; 8];
+        action_bytes.copy_from_slice(actions);
+
+        Ok(Self {
+            actions: u64::from_le_bytes(action_bytes),
+            axes: axes.chunks_exact(2).map(|axis| i16::from_le_bytes([axis[0], axis[1]])).collect()
+        })
+    }
+}
+
+// Only what changed since the previous snapshot, so a quiet tick costs a few bytes
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputDelta {
+    toggled_actions: u64,
+    axis_count: u32,
+    changed_axes: Vec<(u32, i16)>
+}
+
+impl InputDelta {
+    pub fn changed_axes(&self) -> usize {
+        self.changed_axes.len()
+    }
+}
+
+impl InputSnapshot {
+    pub fn diff(&self, previous: &InputSnapshot) -> InputDelta {
+        InputDelta {
+            toggled_actions: self.actions ^ previous.actions,
+            axis_count: self.axes.len() as u32,
+            changed_axes: self.axes.iter()
+                .enumerate()
+                .filter(|&(i, axis)| previous.axes.get(i) != Some(axis))
+                .map(|(i, &axis)| (i as u32, axis))
+                .collect()
+        }
+    }
+
+    pub fn apply_delta(&self, delta: &InputDelta) -> InputSnapshot {
+        let mut axes = self.axes.clone();
+        axes.resize(delta.axis_count as usize, 0);
+
+        for &(i, axis) in &delta.changed_axes {
+            if let Some(slot) = axes.get_mut(i as usize) {
+                *slot = axis;
+            }
+        }
+
+        InputSnapshot {
+            actions: self.actions ^ delta.toggled_actions,
+            axes
+        }
+    }
+}
+
+// Answers the same queries as live input, so local and remote players read alike
+#[derive(Debug, Clone)]
+pub struct PlaybackInput<'a> {
+    layout: &'a InputLayout,
+    snapshot: InputSnapshot
+}
+
+impl PlaybackInput<'_> {
+    pub fn is_action_held(&self, action: &str) -> bool {
+        self.layout.actions.iter()
+            .position(|a| a == action)
+            .is_some_and(|i| self.snapshot.actions & 1 << i != 0)
+    }
+
+    pub fn axis(&self, name: &str) -> f32 {
+        self.layout.axes.iter()
+            .position(|a| a == name)
+            .and_then(|i| self.snapshot.axes.get(i))
+            .map_or(0.0, |&axis| dequantize(axis))
+    }
+}
+
+fn quantize(value: f32) -> i16 {
+    if value.is_nan() {
+        return 0
+    }
+
+    (value.clamp(-1.0, 1.0) * AXIS_SCALE).round() as i16
+}
+
+fn dequantize(axis: i16) -> f32 {
+    (axis as f32 / AXIS_SCALE).max(-1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> InputLayout {
+        InputLayout::new(&["jump", "fire", "crouch"], &["horizontal", "vertical"]).unwrap()
+    }
+
+    fn axis_values(name: &str) -> f32 {
+        match name {
+            "horizontal" => -1.0,
+            "vertical" => 0.5,
+            _ => 0.0
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips_into_playback_input() {
+        let layout = layout();
+        let snapshot = layout.capture(&["fire", "crouch", "unbound"], axis_values);
+
+        let bytes = snapshot.to_bytes();
+        assert_eq!(bytes.len(), 12);
+
+        let playback = layout.playback(InputSnapshot::from_bytes(&bytes).unwrap());
+
+        assert!(!playback.is_action_held("jump"));
+        assert!(playback.is_action_held("fire"));
+        assert!(playback.is_action_held("crouch"));
+        assert!(!playback.is_action_held("unbound"));
+        assert_eq!(playback.axis("horizontal"), -1.0);
+        assert!((playback.axis("vertical") - 0.5).abs() < 1e-4);
+        assert_eq!(playback.axis("unbound"), 0.0);
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_serde() {
+        let snapshot = layout().capture(&["jump"], axis_values);
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        assert_eq!(serde_json::from_str::<InputSnapshot>(&json).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn rejects_malformed_bytes_and_oversized_layouts() {
+        assert_eq!(InputSnapshot::from_bytes(&[0; 7]), Err(InputSnapshotError::InvalidLength(7)));
+        assert_eq!(InputSnapshot::from_bytes(&[0; 9]), Err(InputSnapshotError::InvalidLength(9)));
+
+        let actions: Vec<String> = (0..65).map(|i| format!("action_{}", i)).collect();
+        let actions: Vec<&str> = actions.iter().map(String::as_str).collect();
+        assert_eq!(InputLayout::new(&actions, &[]), Err(InputSnapshotError::TooManyActions(65)));
+    }
+
+    #[test]
+    fn delta_applied_to_previous_reproduces_next() {
+        let layout = layout();
+        let previous = layout.capture(&["jump", "fire"], axis_values);
+        let next = layout.capture(&["fire", "crouch"], |name| if name == "vertical" { -0.25 } else { axis_values(name) });
+
+        let delta = next.diff(&previous);
+
+        assert_eq!(delta.changed_axes(), 1);
+        assert_eq!(previous.apply_delta(&delta), next);
+    }
+
+    #[test]
+    fn unchanged_input_produces_empty_delta() {
+        let snapshot = layout().capture(&["jump"], axis_values);
+        let delta = snapshot.diff(&snapshot);
+
+        assert_eq!(delta.changed_axes(), 0);
+        assert_eq!(delta.toggled_actions, 0);
+        assert_eq!(snapshot.apply_delta(&delta), snapshot);
+    }
+}
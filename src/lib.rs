@@ -0,0 +1,22 @@
+pub mod logging;
+pub mod combo;
+pub mod registry;
+pub mod clear_values;
+pub mod input_repeat;
+pub mod grid;
+pub mod ecs_util;
+pub mod color_gradient;
+pub mod view;
+pub mod camera_shake;
+pub mod camera_follow;
+pub mod frame_stats;
+pub mod fit;
+pub mod axis;
+pub mod update_rate;
+pub mod tick;
+pub mod sheet_grid;
+pub mod input_snapshot;
+pub mod asset_alias;
+pub mod bounds;
+pub mod atlas_packer;
+pub mod template;
@@ -0,0 +1,235 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing_appender::non_blocking;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{self, RollingFileAppender};
+use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{EnvFilter, Registry};
+
+pub const DEFAULT_LOG_DIR: &str = "C:/Users/tlmor/game_engine_tests/";
+pub const DEFAULT_LOG_FILE_PREFIX: &str = "game_engine.log";
+
+const PRUNE_BYTE_INTERVAL: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub directory: PathBuf,
+    pub file_name_prefix: String,
+    pub max_files: Option<usize>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from(DEFAULT_LOG_DIR),
+            file_name_prefix: String::from(DEFAULT_LOG_FILE_PREFIX),
+            max_files: Some(48),
+            max_total_bytes: Some(512 * 1024 * 1024),
+        }
+    }
+}
+
+pub fn init(app_name: String, config: LogConfig) -> WorkerGuard {
+    if let Err(e) = prune_logs(&config) {
+        eprintln!("Failed to prune old log files: {}", e);
+    }
+
+    let (non_blocking_writer, guard) = non_blocking(RetainingAppender::new(config));
+
+    let bunyan_formatting_layer = BunyanFormattingLayer::new(app_name, non_blocking_writer);
+    let subscriber = Registry::default()
+        .with(EnvFilter::from_default_env())
+        .with(JsonStorageLayer)
+        .with(bunyan_formatting_layer);
+
+    tracing::subscriber::set_global_default(subscriber).expect("Failed to set global default subscriber");
+
+    guard
+}
+
+struct RetainingAppender {
+    inner: RollingFileAppender,
+    config: LogConfig,
+    last_prune_hour: u64,
+    bytes_since_prune: u64,
+}
+
+impl RetainingAppender {
+    fn new(config: LogConfig) -> Self {
+        Self {
+            inner: rolling::hourly(&config.directory, &config.file_name_prefix),
+            config,
+            last_prune_hour: current_hour(),
+            bytes_since_prune: 0,
+        }
+    }
+}
+
+impl Write for RetainingAppender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes_since_prune += written as u64;
+
+        // The rolling appender switches files inside write, so pruning afterwards never removes the live file
+        let hour = current_hour();
+        if hour != self.last_prune_hour || self.bytes_since_prune >= PRUNE_BYTE_INTERVAL {
+            self.last_prune_hour = hour;
+            self.bytes_since_prune = 0;
+
+            if let Err(e) = prune_logs(&self.config) {
+                eprintln!("Failed to prune old log files: {}", e);
+            }
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn current_hour() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 3600)
+        .unwrap_or(0)
+}
+
+fn prune_logs(config: &LogConfig) -> io::Result<()> {
+    if !config.directory.is_dir() {
+        return Ok(())
+    }
+
+    let mut logs = Vec::new();
+    for entry in fs::read_dir(&config.directory)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_file() && is_rolled_log(&entry.file_name().to_string_lossy(), &config.file_name_prefix) {
+            logs.push((entry.path(), metadata.len()));
+        }
+    }
+
+    // Hourly files are suffixed with the date and hour, so name order is age order
+    logs.sort();
+
+    let mut count = logs.len();
+    let mut total_bytes: u64 = logs.iter().map(|(_, len)| len).sum();
+    let newest = logs.len().saturating_sub(1);
+
+    for (path, len) in &logs[..newest] {
+        let over_count = config.max_files.is_some_and(|max| count > max);
+        let over_size = config.max_total_bytes.is_some_and(|max| total_bytes > max);
+
+        if !over_count && !over_size {
+            break
+        }
+
+        // A locked file (e.g. open in a viewer on Windows) is skipped so the remaining old files still get pruned
+        if let Err(e) = fs::remove_file(path) {
+            eprintln!("Failed to remove old log file {}: {}", path.display(), e);
+            continue
+        }
+
+        count -= 1;
+        total_bytes -= len;
+    }
+
+    Ok(())
+}
+
+// Hourly files are named "<prefix>.YYYY-MM-DD-HH"; anything else sharing the prefix (backups, notes) is left alone
+fn is_rolled_log(file_name: &str, prefix: &str) -> bool {
+    let suffix = match file_name.strip_prefix(prefix).and_then(|rest| rest.strip_prefix('.')) {
+        Some(suffix) => suffix,
+        None => return false
+    };
+
+    suffix.len() == 13 && suffix.char_indices().all(|(i, c)| match i {
+        4 | 7 | 10 => c == '-',
+        _ => c.is_ascii_digit()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sprite_render_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Failed to create temp log dir");
+
+        dir
+    }
+
+    fn write_log(dir: &PathBuf, name: &str, bytes: usize) {
+        fs::write(dir.join(name), vec![b'x'; bytes]).expect("Failed to write log file");
+    }
+
+    fn config(dir: &PathBuf, max_files: Option<usize>, max_total_bytes: Option<u64>) -> LogConfig {
+        LogConfig {
+            directory: dir.clone(),
+            file_name_prefix: String::from(DEFAULT_LOG_FILE_PREFIX),
+            max_files,
+            max_total_bytes
+        }
+    }
+
+    #[test]
+    fn prune_deletes_oldest_files_past_max_count() {
+        let dir = temp_log_dir("max_count");
+        for hour in 10..15 {
+            write_log(&dir, &format!("game_engine.log.2021-08-01-{}", hour), 10);
+        }
+
+        prune_logs(&config(&dir, Some(3), None)).unwrap();
+
+        assert!(!dir.join("game_engine.log.2021-08-01-10").exists());
+        assert!(!dir.join("game_engine.log.2021-08-01-11").exists());
+        assert!(dir.join("game_engine.log.2021-08-01-12").exists());
+        assert!(dir.join("game_engine.log.2021-08-01-14").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_deletes_oldest_files_past_max_size_but_keeps_newest() {
+        let dir = temp_log_dir("max_size");
+        write_log(&dir, "game_engine.log.2021-08-01-10", 100);
+        write_log(&dir, "game_engine.log.2021-08-01-11", 100);
+        write_log(&dir, "game_engine.log.2021-08-01-12", 500);
+
+        prune_logs(&config(&dir, None, Some(150))).unwrap();
+
+        assert!(!dir.join("game_engine.log.2021-08-01-10").exists());
+        assert!(!dir.join("game_engine.log.2021-08-01-11").exists());
+        assert!(dir.join("game_engine.log.2021-08-01-12").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_ignores_files_without_an_hourly_suffix() {
+        let dir = temp_log_dir("unrelated");
+        write_log(&dir, "game_engine.log.bak", 10);
+        write_log(&dir, "game_engine.log", 10);
+        write_log(&dir, "game_engine.log.2021-08-01-10", 10);
+        write_log(&dir, "game_engine.log.2021-08-01-11", 10);
+
+        prune_logs(&config(&dir, Some(1), None)).unwrap();
+
+        assert!(dir.join("game_engine.log.bak").exists());
+        assert!(dir.join("game_engine.log").exists());
+        assert!(!dir.join("game_engine.log.2021-08-01-10").exists());
+        assert!(dir.join("game_engine.log.2021-08-01-11").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -12,37 +12,45 @@ use game_engine::graphics::render::sprite_renderer::{SpriteRenderer, SpriteRende
 use anyhow::{Result, Error};
 use luminance_glfw::GL33Context;
 use luminance_front::context::GraphicsContext;
-use luminance_front::pipeline::{PipelineState};
+use luminance_front::pipeline::{PipelineError, PipelineState, Viewport};
+use luminance_front::scissor::ScissorRegion;
 use glam::{Mat4, Vec3};
-use specs::{World, WorldExt};
+use specs::{Entity, World, WorldExt, Join};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use game_engine::components::{ComponentMux, ComponentLoader};
 use std::marker::PhantomData;
 use luminance_front::texture::Dim2;
 use game_engine::game_loop::{GameLoop, GameLoopError};
 use game_engine::input::multi_input::MultiInput;
 use luminance_windowing::{WindowOpt, WindowDim};
-use tracing_appender::non_blocking;
-use tracing_subscriber::{Registry, EnvFilter};
-use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
-use tracing_subscriber::layer::SubscriberExt;
 use game_engine::graphics::render::Renderer;
 use std::sync::{RwLock, Arc};
 use game_engine::camera::orthographic_camera::{OrthographicCameraLoader, ORTHOGRAPHIC_CAMERA_LOAD_ID};
 use game_engine::camera::Camera;
+use sprite_render_test::logging::{self, LogConfig};
+use sprite_render_test::registry::{RegisteredComponent, register_all, verify_registered, list_components};
+use sprite_render_test::color_gradient::ColorGradient;
+use sprite_render_test::view::{ViewAdjust, orthographic_projection};
+use sprite_render_test::camera_shake::CameraShake;
+use sprite_render_test::camera_follow::CameraFollow;
+use sprite_render_test::fit::{FitMode, FitRect};
+use sprite_render_test::asset_alias::AliasTable;
+use sprite_render_test::clear_values::{ClearValues, ClearOverride, deserialize_clear_override};
+use crate::error_scene::{ErrorBoundary, BoundaryPolicy};
+use std::time::Instant;
+use std::collections::HashSet;
+
+#[cfg(feature = "stress_test")]
+use crate::stress_test_scene::{StressTestSceneLoader, STRESS_TEST_SCENE_ID};
+
+mod error_scene;
+#[cfg(feature = "stress_test")]
+mod stress_test_scene;
 
 fn main() -> Result<(), GameLoopError> {
     let app_name = concat!(env!("CARGO_PKG_NAME"), "-", env!("CARGO_PKG_VERSION")).to_string();
-    let file_appender = tracing_appender::rolling::never("C:/Users/tlmor/game_engine_tests/", "game_engine.log");
-    let (non_blocking_writer, _guard) = non_blocking(file_appender);
-
-    let bunyan_formatting_layer = BunyanFormattingLayer::new(app_name, non_blocking_writer);
-    let subscriber = Registry::default()
-        .with(EnvFilter::from_default_env())
-        .with(JsonStorageLayer)
-        .with(bunyan_formatting_layer);
-
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set global default subscriber");
+    let _guard = logging::init(app_name, LogConfig::default());
 
     let game_loop: GameLoop<TestGameWrapper<MultiInput>, MultiInput> = GameLoop::new();
     game_loop.run(
@@ -63,8 +71,12 @@ struct TestGameWrapper<T: Input + Debug> {
 
 impl<T: 'static + Input + Debug> TestGameWrapper<T> {
     fn scene_factory(json: JSONLoad) -> Result<Box<dyn SceneLoader<T>>> {
+        let aliases = AliasTable::load([LOAD_PATH, ALIASES_FILE_ID, JSON_FILE].join(""))?;
+
         match json.load_type_id.as_str() {
-            SPRITE_RENDER_SCENE_ID => Ok(Box::new(SpriteRenderSceneLoader::new([LOAD_PATH, SCENES_DIR, SPRITE_RENDER_SCENE_ID, JSON_FILE].join("")))),
+            SPRITE_RENDER_SCENE_ID => Ok(Box::new(SpriteRenderSceneLoader::new(scene_path(&aliases, SPRITE_RENDER_SCENE_ID)))),
+            #[cfg(feature = "stress_test")]
+            STRESS_TEST_SCENE_ID => Ok(Box::new(StressTestSceneLoader::new(scene_path(&aliases, STRESS_TEST_SCENE_ID)))),
             _ => {Err(Error::msg("Load ID did not match any scene ID"))}
         }
     }
@@ -72,8 +84,8 @@ impl<T: 'static + Input + Debug> TestGameWrapper<T> {
 
 impl<T: 'static + Input + Debug> GameWrapper<T> for TestGameWrapper<T> {
     fn register_components(ecs: &mut World) {
-        ecs.register::<TextureHandle>();
-        ecs.register::<Transform>();
+        register_all(&registered_components(), ecs);
+        verify_registered(&registered_components(), ecs).expect("Failed to register components");
     }
 
     fn load() -> DrawTask<SceneStack<T>> {
@@ -133,54 +145,191 @@ impl<T: 'static + Input + Debug> GameWrapper<T> for TestGameWrapper<T> {
     }
 }
 
+const ALIASES_FILE_ID: &str = "aliases";
+
+// aliases.json can point a scene id at a different file; otherwise the id names its own file
+fn scene_path(aliases: &AliasTable, scene_id: &str) -> String {
+    aliases.get(scene_id)
+        .map(String::from)
+        .unwrap_or_else(|| [LOAD_PATH, SCENES_DIR, scene_id, JSON_FILE].join(""))
+}
+
+fn registered_components() -> [RegisteredComponent; 2] {
+    [
+        RegisteredComponent::of::<TextureHandle>(TEXTURE_LOAD_ID),
+        RegisteredComponent::of::<Transform>(TRANSFORM_LOAD_ID)
+    ]
+}
+
 pub struct SpriteRenderScene<T: Input + Debug> {
     sprite_renderer: SpriteRenderer,
+    clear_color: Option<ColorGradient>,
+    clear_depth: ClearOverride<f32>,
+    clear_stencil: ClearOverride<i32>,
+    fit_mode: FitMode,
+    bar_color: [f32; 4],
+    started_at: Instant,
+    last_update: Instant,
     phantom_input: PhantomData<T>
 }
 
 pub const SPRITE_RENDER_SCENE_ID: &str = "sprite_render_scene";
 
+const DESIGN_WIDTH: f32 = 960.0;
+const DESIGN_HEIGHT: f32 = 540.0;
+
 impl<T: Input + Debug> Debug for SpriteRenderScene<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SpriteRenderScene")
             .field("SpriteRenderer", &self.sprite_renderer.render_state)
+            .field("clear_color", &self.clear_color)
+            .field("clear_depth", &self.clear_depth)
+            .field("clear_stencil", &self.clear_stencil)
+            .field("fit_mode", &self.fit_mode)
+            .field("bar_color", &self.bar_color)
             .finish()
     }
 }
 
+impl<T: Input + Debug> SpriteRenderScene<T> {
+    fn new(sprite_renderer: SpriteRenderer, json: SpriteRenderSceneJSON) -> Self {
+        Self {
+            sprite_renderer,
+            clear_color: json.clear_color,
+            clear_depth: json.clear_depth,
+            clear_stencil: json.clear_stencil,
+            fit_mode: json.fit_mode,
+            bar_color: json.bar_color,
+            started_at: Instant::now(),
+            last_update: Instant::now(),
+            phantom_input: Default::default()
+        }
+    }
+
+    fn pipeline_state(&self, content: FitRect, letterboxed: bool) -> PipelineState {
+        // Clears ignore the viewport, so the scissor is what keeps the content clear off the bars
+        let pipeline_state = if letterboxed {
+            PipelineState::default()
+                .set_viewport(Viewport::Specific {
+                    x: content.x,
+                    y: content.y,
+                    width: content.width,
+                    height: content.height
+                })
+                .set_scissor(ScissorRegion {
+                    x: content.x,
+                    y: content.y,
+                    width: content.width,
+                    height: content.height
+                })
+        } else {
+            PipelineState::default().set_viewport(Viewport::Whole)
+        };
+
+        self.apply_clear_values(pipeline_state)
+    }
+
+    // Evaluated every frame so the view can follow game state, e.g. a zoom punch on impact
+    fn projection(&self, ecs: &World) -> Mat4 {
+        let view = ecs.try_fetch::<ViewAdjust>()
+            .map(|view| *view)
+            .unwrap_or_default();
+        let view = match ecs.try_fetch::<CameraShake>() {
+            Some(shake) => shake.apply(view),
+            None => view
+        };
+
+        orthographic_projection(DESIGN_WIDTH, DESIGN_HEIGHT, &view)
+    }
+}
+
+impl<T: Input + Debug> ClearValues for SpriteRenderScene<T> {
+    fn clear_color(&self) -> ClearOverride<[f32; 4]> {
+        let elapsed = self.started_at.elapsed().as_secs_f32();
+
+        Some(self.clear_color.as_ref().map(|gradient| gradient.evaluate(elapsed)))
+    }
+
+    fn clear_depth(&self) -> ClearOverride<f32> {
+        self.clear_depth
+    }
+
+    fn clear_stencil(&self) -> ClearOverride<i32> {
+        self.clear_stencil
+    }
+}
+
 impl<T: Input + Debug> Scene<T> for SpriteRenderScene<T> {
-    fn update(&mut self, _ecs: &mut World) -> Result<SceneTransition<T>> {
+    fn update(&mut self, ecs: &mut World) -> Result<SceneTransition<T>> {
+        let now = Instant::now();
+        let dt = now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+
+        if let Some(mut shake) = ecs.try_fetch_mut::<CameraShake>() {
+            shake.update(dt);
+        }
+
+        if let Some(follow) = ecs.try_fetch::<CameraFollow>().map(|follow| *follow) {
+            let view = ecs.try_fetch::<ViewAdjust>()
+                .map(|view| *view)
+                .unwrap_or_default();
+            ecs.insert(follow.update(view, DESIGN_WIDTH, DESIGN_HEIGHT, dt));
+        }
+
         Ok(SceneTransition::NONE)
     }
 
     fn draw(&mut self, ecs: &mut World, context: &mut GL33Context) -> Result<()> {
         let back_buffer = context.back_buffer()
             .expect("Failed to get back buffer");
+        let framebuffer_size = back_buffer.size();
+        let content = self.fit_mode.content_rect(framebuffer_size, [DESIGN_WIDTH, DESIGN_HEIGHT]);
+        let letterboxed = content != FitRect::whole(framebuffer_size);
+
+        if letterboxed {
+            // Only the bar colour; depth and stencil are left to the content pass so its clear overrides still hold
+            let bars_state = PipelineState::default()
+                .set_clear_color(self.bar_color)
+                .set_clear_depth(None)
+                .set_clear_stencil(None);
+
+            context.new_pipeline_gate()
+                .pipeline::<PipelineError, Dim2, (), (), _>(
+                    &back_buffer,
+                    &bars_state,
+                    |_, _| Ok(())
+                )
+                .into_result()
+                .map_err(|e| {
+                    tracing::error!(scene = %self.get_name(), error = %e, "Failed to clear letterbox bars");
+                    Error::new(e)
+                })?;
+        }
 
-        context.new_pipeline_gate()
+        let pipeline_state = self.pipeline_state(content, letterboxed);
+        let projection = self.projection(ecs);
+
+        let render = context.new_pipeline_gate()
             .pipeline::<SpriteRenderError, Dim2, (), (), _>(
                 &back_buffer,
-                &PipelineState::default().set_clear_color([0.0, 0.0, 0.0, 1.0]),
+                &pipeline_state,
                 |pipeline, mut shading_gate| {
                     self.sprite_renderer.render(
                         &pipeline,
                         &mut shading_gate,
-                        &Mat4::orthographic_rh_gl(
-                            0.0,
-                            960.0,
-                            0.0,
-                                540.0,
-                            -1.0,
-                            10.0
-                        ),
-                ecs
-                    ).unwrap();
+                        &projection,
+                        ecs
+                    )?;
 
                     Ok(())
                 }
             );
-        
-        Ok(())
+
+        render.into_result()
+            .map_err(|e| {
+                tracing::error!(scene = %self.get_name(), error = %e, "Failed to render sprites");
+                Error::new(e)
+            })
     }
 
     fn interact(&mut self, _ecs: &mut World, _input: &T) -> Result<()> {
@@ -198,7 +347,28 @@ impl<T: Input + Debug> Scene<T> for SpriteRenderScene<T> {
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpriteRenderSceneJSON {
-    entity_paths: Vec<String>
+    entity_paths: Vec<String>,
+    // null skips the color clear, for overlays drawn on top of another scene
+    #[serde(default = "default_clear_color")]
+    clear_color: Option<ColorGradient>,
+    // Missing keeps the PipelineState default, null skips the clear (see ClearValues for overlay scenes)
+    #[serde(default, deserialize_with = "deserialize_clear_override")]
+    clear_depth: ClearOverride<f32>,
+    #[serde(default, deserialize_with = "deserialize_clear_override")]
+    clear_stencil: ClearOverride<i32>,
+    #[serde(default)]
+    fit_mode: FitMode,
+    // Only drawn when fit_mode leaves bars around the content
+    #[serde(default = "default_bar_color")]
+    bar_color: [f32; 4]
+}
+
+fn default_clear_color() -> Option<ColorGradient> {
+    Some(ColorGradient::constant([0.0, 0.0, 0.0, 1.0]))
+}
+
+fn default_bar_color() -> [f32; 4] {
+    [0.0, 0.0, 0.0, 1.0]
 }
 
 #[derive(Debug)]
@@ -228,31 +398,58 @@ impl<T: Input + Debug> ComponentMux for SpriteRenderSceneLoader<T> {
 
 impl<T: 'static + Input + Debug> SceneLoader<T> for SpriteRenderSceneLoader<T> {
     fn load_scene(&self) -> DrawTask<Box<dyn Scene<T>>> {
-        let path = self.path.clone();
-
-        SpriteRendererLoader::load_default()
-            .join(
-                DrawTask::new(move |_| {
-                    let json: SpriteRenderSceneJSON = load_deserializable_from_file(&path, SPRITE_RENDER_SCENE_ID)
-                        .map_err(|e| {
-                            Error::new(e)
-                        })?;
-
-                    return Ok(json)
-                }),
-                |args| return args
-            )
-            .serialize(
-                Task::new(|((renderer, json),(ecs, context)): ((SpriteRenderer, SpriteRenderSceneJSON),(Arc<RwLock<World>>, Arc<RwLock<GL33Context>>))| {
-                    create_entity_vec::<Self>(&json.entity_paths, ecs, context)?;
-                    return Ok(renderer)
-                })
-            )
-            .map(|renderer, (_ecs, _context)| {
-                Ok(Box::new(SpriteRenderScene {
-                    sprite_renderer: renderer,
-                    phantom_input: Default::default()
-                }) as Box<dyn Scene<T>>)
+        load_sprite_scene::<T, SpriteRenderSceneJSON>(self.path.clone(), SPRITE_RENDER_SCENE_ID)
+            .map(|(renderer, json), (_ecs, _context)| {
+                Ok(Box::new(ErrorBoundary::new(
+                    Box::new(SpriteRenderScene::new(renderer, json)),
+                    BoundaryPolicy::default()
+                )) as Box<dyn Scene<T>>)
             })
     }
-}
\ No newline at end of file
+}
+
+// Scene JSON loaded by load_sprite_scene; entity_paths lists the entity files to spawn, repeats allowed
+pub trait SpriteSceneJSON: 'static + Send + Sync + DeserializeOwned {
+    fn entity_paths(&self) -> Vec<String>;
+}
+
+impl SpriteSceneJSON for SpriteRenderSceneJSON {
+    fn entity_paths(&self) -> Vec<String> {
+        self.entity_paths.clone()
+    }
+}
+
+// Loads the sprite renderer and the scene JSON side by side, then spawns the scene's entities
+pub fn load_sprite_scene<T: 'static + Input + Debug, J: SpriteSceneJSON>(path: String, load_id: &'static str) -> DrawTask<(SpriteRenderer, J)> {
+    SpriteRendererLoader::load_default()
+        .join(
+            DrawTask::new(move |_| {
+                let json: J = load_deserializable_from_file(&path, load_id)
+                    .map_err(|e| {
+                        Error::new(e)
+                    })?;
+
+                return Ok(json)
+            }),
+            |args| return args
+        )
+        .serialize(
+            Task::new(|((renderer, json),(ecs, context)): ((SpriteRenderer, J),(Arc<RwLock<World>>, Arc<RwLock<GL33Context>>))| {
+                let existing: HashSet<Entity> = ecs.read()
+                    .expect("Failed to acquire read lock for World")
+                    .entities()
+                    .join()
+                    .collect();
+
+                create_entity_vec::<SpriteRenderSceneLoader<T>>(&json.entity_paths(), ecs.clone(), context)?;
+
+                // Only log what this load spawned, not entities left over from earlier scenes
+                let world = ecs.read().expect("Failed to acquire read lock for World");
+                for entity in world.entities().join().filter(|entity| !existing.contains(entity)) {
+                    tracing::debug!(entity = entity.id(), components = ?list_components(&registered_components(), &world, entity), "Loaded entity");
+                }
+
+                return Ok((renderer, json))
+            })
+        )
+}
@@ -0,0 +1,127 @@
+use specs::storage::MaskedStorage;
+use specs::{Component, Entity, World, WorldExt};
+
+// One entry per component type a game registers, so registration and inspection share a single list
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredComponent {
+    pub load_id: &'static str,
+    register_fn: fn(&mut World),
+    contains_fn: fn(&World, Entity) -> bool,
+    verify_fn: fn(&World) -> bool
+}
+
+impl RegisteredComponent {
+    pub fn of<C>(load_id: &'static str) -> Self
+        where C: Component, C::Storage: Default {
+        Self {
+            load_id,
+            register_fn: register::<C>,
+            contains_fn: contains::<C>,
+            verify_fn: verify::<C>
+        }
+    }
+
+    pub fn register(&self, ecs: &mut World) {
+        (self.register_fn)(ecs)
+    }
+
+    pub fn is_present(&self, ecs: &World, entity: Entity) -> bool {
+        (self.contains_fn)(ecs, entity)
+    }
+
+    pub fn is_registered(&self, ecs: &World) -> bool {
+        (self.verify_fn)(ecs)
+    }
+}
+
+fn register<C>(ecs: &mut World)
+    where C: Component, C::Storage: Default {
+    ecs.register::<C>();
+}
+
+fn contains<C: Component>(ecs: &World, entity: Entity) -> bool {
+    ecs.read_storage::<C>().contains(entity)
+}
+
+// read_storage panics on an unregistered component, so look for the storage resource itself
+fn verify<C: Component>(ecs: &World) -> bool {
+    ecs.has_value::<MaskedStorage<C>>()
+}
+
+pub fn register_all(components: &[RegisteredComponent], ecs: &mut World) {
+    for component in components {
+        component.register(ecs);
+    }
+}
+
+// Run at startup so a forgotten registration names its load id instead of panicking later inside a loader
+pub fn verify_registered(components: &[RegisteredComponent], ecs: &World) -> Result<(), Vec<&'static str>> {
+    let missing: Vec<&'static str> = components.iter()
+        .filter(|component| !component.is_registered(ecs))
+        .map(|component| component.load_id)
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(missing)
+    }
+}
+
+pub fn list_components(components: &[RegisteredComponent], ecs: &World, entity: Entity) -> Vec<&'static str> {
+    components.iter()
+        .filter(|component| component.is_present(ecs, entity))
+        .map(|component| component.load_id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, NullStorage, VecStorage};
+
+    struct Position;
+
+    impl Component for Position {
+        type Storage = VecStorage<Self>;
+    }
+
+    #[derive(Default)]
+    struct Sprite;
+
+    impl Component for Sprite {
+        type Storage = NullStorage<Self>;
+    }
+
+    fn components() -> [RegisteredComponent; 2] {
+        [
+            RegisteredComponent::of::<Sprite>("sprite"),
+            RegisteredComponent::of::<Position>("position")
+        ]
+    }
+
+    #[test]
+    fn lists_load_ids_of_present_components() {
+        let mut ecs = World::new();
+        register_all(&components(), &mut ecs);
+
+        let both = ecs.create_entity().with(Position).with(Sprite).build();
+        let position_only = ecs.create_entity().with(Position).build();
+        let empty = ecs.create_entity().build();
+
+        assert_eq!(list_components(&components(), &ecs, both), vec!["sprite", "position"]);
+        assert_eq!(list_components(&components(), &ecs, position_only), vec!["position"]);
+        assert!(list_components(&components(), &ecs, empty).is_empty());
+    }
+
+    #[test]
+    fn verify_reports_components_left_unregistered() {
+        let mut ecs = World::new();
+        RegisteredComponent::of::<Sprite>("sprite").register(&mut ecs);
+
+        assert_eq!(verify_registered(&components(), &ecs), Err(vec!["position"]));
+
+        register_all(&components(), &mut ecs);
+        assert_eq!(verify_registered(&components(), &ecs), Ok(()));
+    }
+}
@@ -0,0 +1,105 @@
+// Uniform grid layout as written by common sprite sheet exporters, all in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSlice {
+    pub cell_width: u32,
+    pub cell_height: u32,
+    // Border around the whole sheet
+    pub margin: u32,
+    // Gap between neighbouring cells
+    pub spacing: u32
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SheetRegion {
+    pub index: usize,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    // [u_min, v_min, u_max, v_max] with v measured from the top of the sheet like the pixel rect
+    pub uv: [f32; 4]
+}
+
+// Regions are numbered row by row from the top left; partial cells at the right or bottom edge are dropped
+pub fn slice_grid([sheet_width, sheet_height]: [u32; 2], slice: &GridSlice) -> Vec<SheetRegion> {
+    if slice.cell_width == 0 || slice.cell_height == 0 || sheet_width == 0 || sheet_height == 0 {
+        return Vec::new()
+    }
+
+    let columns = fit_cells(sheet_width, slice.cell_width, slice.margin, slice.spacing);
+    let rows = fit_cells(sheet_height, slice.cell_height, slice.margin, slice.spacing);
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (row, column)))
+        .enumerate()
+        .map(|(index, (row, column))| {
+            let x = slice.margin + column * (slice.cell_width + slice.spacing);
+            let y = slice.margin + row * (slice.cell_height + slice.spacing);
+
+            SheetRegion {
+                index,
+                x,
+                y,
+                width: slice.cell_width,
+                height: slice.cell_height,
+                uv: [
+                    x as f32 / sheet_width as f32,
+                    y as f32 / sheet_height as f32,
+                    (x + slice.cell_width) as f32 / sheet_width as f32,
+                    (y + slice.cell_height) as f32 / sheet_height as f32
+                ]
+            }
+        })
+        .collect()
+}
+
+fn fit_cells(sheet: u32, cell: u32, margin: u32, spacing: u32) -> u32 {
+    let usable = sheet.saturating_sub(margin.saturating_mul(2));
+    if usable < cell {
+        return 0
+    }
+
+    1 + (usable - cell) / (cell + spacing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(cell: u32, margin: u32, spacing: u32) -> GridSlice {
+        GridSlice {
+            cell_width: cell,
+            cell_height: cell,
+            margin,
+            spacing
+        }
+    }
+
+    #[test]
+    fn slices_64x32_sheet_into_eight_16px_cells() {
+        let regions = slice_grid([64, 32], &cells(16, 0, 0));
+
+        assert_eq!(regions.len(), 8);
+        assert_eq!(regions[0].uv, [0.0, 0.0, 0.25, 0.5]);
+        assert_eq!(regions[3].uv, [0.75, 0.0, 1.0, 0.5]);
+        assert_eq!((regions[5].index, regions[5].x, regions[5].y), (5, 16, 16));
+        assert_eq!(regions[5].uv, [0.25, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn margin_and_spacing_offset_cells() {
+        // 2px border and 2px gaps: 2 + 16 + 2 + 16 + 2 = 38 wide fits two columns, one row
+        let regions = slice_grid([38, 20], &cells(16, 2, 2));
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!((regions[0].x, regions[0].y), (2, 2));
+        assert_eq!((regions[1].x, regions[1].y), (20, 2));
+    }
+
+    #[test]
+    fn degenerate_grids_produce_no_regions() {
+        assert!(slice_grid([64, 32], &cells(0, 0, 0)).is_empty());
+        assert!(slice_grid([8, 8], &cells(16, 0, 0)).is_empty());
+        assert!(slice_grid([16, 16], &cells(16, 20, 0)).is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use game_engine::graphics::render::sprite_renderer::SpriteRenderer;
+use game_engine::input::Input;
+use game_engine::loading::DrawTask;
+use game_engine::scenes::scene_stack::SceneTransition;
+use game_engine::scenes::{Scene, SceneLoader};
+use luminance_glfw::GL33Context;
+use serde::{Deserialize, Deserializer};
+use specs::World;
+use thiserror::Error;
+use sprite_render_test::frame_stats::{FrameBudget, FrameStatsRecorder, frame_budget_from_millis, report_interval_from_secs};
+
+use crate::{SpriteRenderScene, SpriteRenderSceneJSON, SpriteSceneJSON, load_sprite_scene};
+use crate::error_scene::{BoundaryPolicy, ErrorBoundary};
+
+pub const STRESS_TEST_SCENE_ID: &str = "stress_test_scene";
+
+const BUDGET_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct StressTestScene<T: Input + Debug> {
+    scene: SpriteRenderScene<T>,
+    sprite_count: usize,
+    frame_stats: FrameStatsRecorder,
+    frame_budget: FrameBudget,
+    last_draw: Option<Instant>
+}
+
+impl<T: Input + Debug> StressTestScene<T> {
+    fn new(sprite_renderer: SpriteRenderer, json: StressTestSceneJSON) -> Self {
+        Self {
+            scene: SpriteRenderScene::new(sprite_renderer, json.scene),
+            sprite_count: json.sprite_count,
+            frame_stats: FrameStatsRecorder::new(json.report_interval),
+            frame_budget: FrameBudget::new(json.frame_budget, BUDGET_WARNING_INTERVAL),
+            last_draw: None
+        }
+    }
+}
+
+impl<T: Input + Debug> Scene<T> for StressTestScene<T> {
+    fn update(&mut self, ecs: &mut World) -> Result<SceneTransition<T>> {
+        self.scene.update(ecs)
+    }
+
+    fn draw(&mut self, ecs: &mut World, context: &mut GL33Context) -> Result<()> {
+        let draw_start = Instant::now();
+        let result = self.scene.draw(ecs, context);
+        let draw_time = draw_start.elapsed();
+
+        // Measured draw to draw, so the frame time also covers update and the rest of the game loop
+        if let Some(last_draw) = self.last_draw.replace(draw_start) {
+            let frame_time = draw_start.saturating_duration_since(last_draw);
+
+            if self.frame_budget.check(frame_time, draw_start) {
+                tracing::warn!(
+                    scene = %self.get_name(),
+                    budget_ms = self.frame_budget.budget().as_secs_f32() * 1000.0,
+                    frame_ms = frame_time.as_secs_f32() * 1000.0,
+                    draw_ms = draw_time.as_secs_f32() * 1000.0,
+                    other_ms = frame_time.saturating_sub(draw_time).as_secs_f32() * 1000.0,
+                    "Frame exceeded its budget"
+                );
+            }
+        }
+
+        if let Some(stats) = self.frame_stats.record_frame(draw_time, Instant::now()) {
+            tracing::info!(
+                scene = %self.get_name(),
+                sprites = self.sprite_count,
+                frames = stats.frames,
+                fps = stats.fps,
+                avg_draw_ms = stats.avg_draw_ms,
+                one_percent_low_fps = stats.one_percent_low_fps,
+                "Stress test frame stats"
+            );
+
+            ecs.insert(stats);
+        }
+
+        result
+    }
+
+    fn interact(&mut self, ecs: &mut World, input: &T) -> Result<()> {
+        self.scene.interact(ecs, input)
+    }
+
+    fn get_name(&self) -> String {
+        String::from("Sprite Render Stress Test Scene")
+    }
+
+    fn is_finished(&self, ecs: &mut World) -> Result<bool> {
+        self.scene.is_finished(ecs)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "StressTestSceneFields")]
+pub struct StressTestSceneJSON {
+    scene: SpriteRenderSceneJSON,
+    sprite_count: usize,
+    report_interval: Duration,
+    frame_budget: Duration
+}
+
+#[derive(Error, Debug)]
+pub enum StressTestSceneError {
+    #[error("Stress test scene needs at least one entity path to spawn {0} sprites")]
+    NoEntityPaths(usize)
+}
+
+// Checked as a whole once every field has parsed, since the sprite count and entity paths only make sense together
+#[derive(Deserialize)]
+struct StressTestSceneFields {
+    #[serde(flatten)]
+    scene: SpriteRenderSceneJSON,
+    sprite_count: usize,
+    #[serde(default = "default_report_interval", deserialize_with = "deserialize_report_interval")]
+    report_interval: Duration,
+    #[serde(default = "default_frame_budget", deserialize_with = "deserialize_frame_budget", rename = "frame_budget_ms")]
+    frame_budget: Duration
+}
+
+impl TryFrom<StressTestSceneFields> for StressTestSceneJSON {
+    type Error = StressTestSceneError;
+
+    fn try_from(fields: StressTestSceneFields) -> Result<Self, Self::Error> {
+        // Otherwise cycling over no paths would quietly spawn nothing
+        if fields.sprite_count > 0 && fields.scene.entity_paths.is_empty() {
+            return Err(StressTestSceneError::NoEntityPaths(fields.sprite_count))
+        }
+
+        Ok(Self {
+            scene: fields.scene,
+            sprite_count: fields.sprite_count,
+            report_interval: fields.report_interval,
+            frame_budget: fields.frame_budget
+        })
+    }
+}
+
+impl SpriteSceneJSON for StressTestSceneJSON {
+    // Repeat the scene's entity files until the requested number of sprites is reached
+    fn entity_paths(&self) -> Vec<String> {
+        self.scene.entity_paths.iter()
+            .cycle()
+            .take(self.sprite_count)
+            .cloned()
+            .collect()
+    }
+}
+
+fn default_report_interval() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn default_frame_budget() -> Duration {
+    Duration::from_micros(16_667)
+}
+
+fn deserialize_frame_budget<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    frame_budget_from_millis(f32::deserialize(deserializer)?)
+        .map_err(serde::de::Error::custom)
+}
+
+// A bad interval fails the scene load instead of panicking in Duration::from_secs_f32
+fn deserialize_report_interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    report_interval_from_secs(f32::deserialize(deserializer)?)
+        .map_err(serde::de::Error::custom)
+}
+
+#[derive(Debug)]
+pub struct StressTestSceneLoader<T: Input + Debug> {
+    path: String,
+    phantom_input: PhantomData<T>
+}
+
+impl<T: Input + Debug> StressTestSceneLoader<T> {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            phantom_input: Default::default()
+        }
+    }
+}
+
+impl<T: 'static + Input + Debug> SceneLoader<T> for StressTestSceneLoader<T> {
+    fn load_scene(&self) -> DrawTask<Box<dyn Scene<T>>> {
+        load_sprite_scene::<T, StressTestSceneJSON>(self.path.clone(), STRESS_TEST_SCENE_ID)
+            .map(|(renderer, json), (_ecs, _context)| {
+                tracing::info!(sprites = json.sprite_count, "Spawned stress test sprites");
+
+                Ok(Box::new(ErrorBoundary::new(
+                    Box::new(StressTestScene::new(renderer, json)),
+                    BoundaryPolicy::default()
+                )) as Box<dyn Scene<T>>)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(sprite_count: usize, report_interval: &str) -> serde_json::Result<StressTestSceneJSON> {
+        serde_json::from_str(&format!(
+            r#"{{ "entity_paths": ["a.json", "b.json"], "sprite_count": {}, "report_interval": {} }}"#,
+            sprite_count,
+            report_interval
+        ))
+    }
+
+    #[test]
+    fn entity_paths_cycle_up_to_sprite_count() {
+        let json = json(5, "1.0").unwrap();
+
+        assert_eq!(json.entity_paths(), vec!["a.json", "b.json", "a.json", "b.json", "a.json"]);
+    }
+
+    #[test]
+    fn invalid_report_interval_fails_to_load() {
+        assert!(json(5, "-1.0").is_err());
+        assert!(json(5, "0.0").is_err());
+        assert!(json(5, "1e39").is_err());
+        assert_eq!(json(5, "0.25").unwrap().report_interval, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn frame_budget_defaults_and_is_validated() {
+        assert_eq!(json(5, "1.0").unwrap().frame_budget, Duration::from_micros(16_667));
+
+        let negative = r#"{ "entity_paths": ["a.json"], "sprite_count": 1, "frame_budget_ms": -5.0 }"#;
+        assert!(serde_json::from_str::<StressTestSceneJSON>(negative).is_err());
+    }
+
+    #[test]
+    fn empty_entity_paths_fail_to_load_when_sprites_are_requested() {
+        let empty = |sprite_count| serde_json::from_str::<StressTestSceneJSON>(&format!(
+            r#"{{ "entity_paths": [], "sprite_count": {} }}"#,
+            sprite_count
+        ));
+
+        assert!(empty(10).is_err());
+        assert!(empty(0).unwrap().entity_paths().is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+pub const EXTENDS_KEY: &str = "extends";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Template {0} was not found")]
+    Missing(String),
+    #[error("Template {0} must be a JSON object")]
+    NotAnObject(String),
+    #[error("Template {0} has an \"extends\" that is not a template name")]
+    InvalidExtends(String),
+    #[error("Templates extend each other in a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>)
+}
+
+// Walks the extends chain of `name` up to its root and merges from the root down, so the most derived field wins
+pub fn resolve_template(name: &str, templates: &HashMap<String, Value>) -> Result<Value, TemplateError> {
+    let mut chain: Vec<&str> = Vec::new();
+    let mut current = name;
+
+    loop {
+        if chain.contains(&current) {
+            let mut cycle: Vec<String> = chain.iter().map(|name| name.to_string()).collect();
+            cycle.push(current.to_string());
+
+            return Err(TemplateError::Cycle(cycle))
+        }
+        chain.push(current);
+
+        let template = templates.get(current)
+            .ok_or_else(|| TemplateError::Missing(current.to_string()))?
+            .as_object()
+            .ok_or_else(|| TemplateError::NotAnObject(current.to_string()))?;
+
+        match template.get(EXTENDS_KEY) {
+            None => break,
+            Some(Value::String(base)) => current = base.as_str(),
+            Some(_) => return Err(TemplateError::InvalidExtends(current.to_string()))
+        }
+    }
+
+    let mut merged = Map::new();
+    for name in chain.iter().rev() {
+        if let Some(Value::Object(template)) = templates.get(*name) {
+            merge(&mut merged, template);
+        }
+    }
+    merged.remove(EXTENDS_KEY);
+
+    Ok(Value::Object(merged))
+}
+
+// Nested objects (e.g. component blocks) merge field by field; anything else is replaced outright
+fn merge(base: &mut Map<String, Value>, derived: &Map<String, Value>) {
+    for (key, value) in derived {
+        match (base.get_mut(key), value) {
+            (Some(Value::Object(base_object)), Value::Object(derived_object)) => merge(base_object, derived_object),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn templates(entries: Vec<(&str, Value)>) -> HashMap<String, Value> {
+        entries.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+    }
+
+    #[test]
+    fn three_level_chain_merges_with_derived_winning() {
+        let templates = templates(vec![
+            ("enemy", json!({ "health": 10, "transform": { "scale": 1.0, "rotation": 0.0 }, "texture": "enemy.png" })),
+            ("fast_enemy", json!({ "extends": "enemy", "speed": 8, "transform": { "scale": 0.8 } })),
+            ("fast_boss", json!({ "extends": "fast_enemy", "health": 100, "texture": "boss.png" }))
+        ]);
+
+        let boss = resolve_template("fast_boss", &templates).unwrap();
+
+        assert_eq!(boss, json!({
+            "health": 100,
+            "speed": 8,
+            "texture": "boss.png",
+            "transform": { "scale": 0.8, "rotation": 0.0 }
+        }));
+    }
+
+    #[test]
+    fn cycle_is_reported() {
+        let templates = templates(vec![
+            ("a", json!({ "extends": "b" })),
+            ("b", json!({ "extends": "c" })),
+            ("c", json!({ "extends": "a" }))
+        ]);
+
+        assert_eq!(
+            resolve_template("a", &templates),
+            Err(TemplateError::Cycle(vec!["a".into(), "b".into(), "c".into(), "a".into()]))
+        );
+    }
+
+    #[test]
+    fn missing_base_and_bad_extends_are_errors() {
+        let templates = templates(vec![
+            ("orphan", json!({ "extends": "nobody" })),
+            ("numbered", json!({ "extends": 3 }))
+        ]);
+
+        assert_eq!(resolve_template("orphan", &templates), Err(TemplateError::Missing("nobody".into())));
+        assert_eq!(resolve_template("numbered", &templates), Err(TemplateError::InvalidExtends("numbered".into())));
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+// The authoritative simulation clock: bumped exactly once per fixed update, never derived from wall time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tick(pub u64);
+
+impl Tick {
+    pub fn advance(&mut self) {
+        self.0 += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration
+}
+
+impl FixedTimestep {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step,
+            accumulator: Duration::default()
+        }
+    }
+
+    // Runs as many fixed updates as the frame covered and returns their count; a zero step never ticks
+    pub fn advance(&mut self, frame_time: Duration, tick: &mut Tick) -> u32 {
+        if self.step == Duration::default() {
+            return 0
+        }
+
+        self.accumulator += frame_time;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            self.accumulator -= self.step;
+            tick.advance();
+            steps += 1;
+        }
+
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticks_after(frame_time: Duration, frames: u32) -> Tick {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        let mut tick = Tick::default();
+
+        for _ in 0..frames {
+            timestep.advance(frame_time, &mut tick);
+        }
+
+        tick
+    }
+
+    #[test]
+    fn ticks_do_not_depend_on_frame_rate() {
+        // One second of play at 50, 200 and 25 frames per second
+        assert_eq!(ticks_after(Duration::from_millis(20), 50), Tick(100));
+        assert_eq!(ticks_after(Duration::from_millis(5), 200), Tick(100));
+        assert_eq!(ticks_after(Duration::from_millis(40), 25), Tick(100));
+    }
+
+    #[test]
+    fn partial_steps_carry_over() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        let mut tick = Tick::default();
+
+        assert_eq!(timestep.advance(Duration::from_millis(6), &mut tick), 0);
+        assert_eq!(timestep.advance(Duration::from_millis(6), &mut tick), 1);
+        assert_eq!(tick, Tick(1));
+    }
+}
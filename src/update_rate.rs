@@ -0,0 +1,35 @@
+use specs::{Component, VecStorage};
+
+// Systems skip an entity's update except every Nth frame; 0 and 1 both mean every frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateRate(pub u32);
+
+impl Component for UpdateRate {
+    type Storage = VecStorage<Self>;
+}
+
+impl UpdateRate {
+    pub fn should_update(&self, frame: u64) -> bool {
+        self.0 <= 1 || frame.is_multiple_of(u64::from(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_three_updates_once_every_three_frames() {
+        let rate = UpdateRate(3);
+
+        let updated: Vec<u64> = (0..9).filter(|&frame| rate.should_update(frame)).collect();
+
+        assert_eq!(updated, vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn rates_zero_and_one_update_every_frame() {
+        assert!((0..5).all(|frame| UpdateRate(0).should_update(frame)));
+        assert!((0..5).all(|frame| UpdateRate(1).should_update(frame)));
+    }
+}
@@ -0,0 +1,151 @@
+use glam::{Mat4, Vec2, Vec3};
+
+// SpriteRenderScene::projection reads this from the World when one is present; only a CameraFollow inserts it by default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewAdjust {
+    pub zoom: f32,
+    pub offset: Vec2,
+    // Radians, counter-clockwise about the view centre
+    pub rotation: f32
+}
+
+impl Default for ViewAdjust {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            offset: Vec2::ZERO,
+            rotation: 0.0
+        }
+    }
+}
+
+impl ViewAdjust {
+    // The offset and zoom that frame every point with `padding` world units to spare inside a width x height view.
+    // Non-finite points are skipped; with none left, or all on one spot, the zoom stays at 1
+    pub fn fit(width: f32, height: f32, points: &[Vec2], padding: f32) -> Self {
+        let mut finite = points.iter().filter(|point| point.is_finite());
+        let first = match finite.next() {
+            Some(first) => *first,
+            None => return Self::default()
+        };
+        let (min, max) = finite.fold((first, first), |(min, max), point| (min.min(*point), max.max(*point)));
+
+        let padding = if padding.is_finite() { padding.abs() } else { 0.0 };
+        let size = max - min + Vec2::splat(2.0 * padding);
+        let zoom = (width / size.x).min(height / size.y);
+
+        Self {
+            zoom: if zoom.is_finite() && zoom > 0.0 { zoom } else { 1.0 },
+            offset: (min + max) / 2.0 - Vec2::new(width, height) / 2.0,
+            rotation: 0.0
+        }
+    }
+}
+
+// Zooms about the centre of the width x height view; a zoom that is not a positive finite number is treated as 1
+pub fn orthographic_projection(width: f32, height: f32, view: &ViewAdjust) -> Mat4 {
+    let zoom = if view.zoom.is_finite() && view.zoom > 0.0 { view.zoom } else { 1.0 };
+
+    let half_width = width / (2.0 * zoom);
+    let half_height = height / (2.0 * zoom);
+    let center = Vec2::new(width, height) / 2.0 + view.offset;
+
+    let projection = Mat4::orthographic_rh_gl(
+        center.x - half_width,
+        center.x + half_width,
+        center.y - half_height,
+        center.y + half_height,
+        -1.0,
+        10.0
+    );
+
+    if view.rotation == 0.0 {
+        return projection
+    }
+
+    // Rotating the view one way turns the world the other way around the centre
+    let center = Vec3::new(center.x, center.y, 0.0);
+    projection
+        * Mat4::from_translation(center)
+        * Mat4::from_rotation_z(-view.rotation)
+        * Mat4::from_translation(-center)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projected_width(projection: Mat4, left: f32, right: f32) -> f32 {
+        projection.transform_point3(Vec3::new(right, 270.0, 0.0)).x
+            - projection.transform_point3(Vec3::new(left, 270.0, 0.0)).x
+    }
+
+    #[test]
+    fn default_view_matches_plain_orthographic_projection() {
+        let expected = Mat4::orthographic_rh_gl(0.0, 960.0, 0.0, 540.0, -1.0, 10.0);
+
+        assert!(orthographic_projection(960.0, 540.0, &ViewAdjust::default()).abs_diff_eq(expected, 1e-6));
+    }
+
+    #[test]
+    fn zoom_scales_sprite_on_screen() {
+        let normal = orthographic_projection(960.0, 540.0, &ViewAdjust::default());
+        let zoomed = orthographic_projection(960.0, 540.0, &ViewAdjust { zoom: 2.0, ..Default::default() });
+
+        let normal_width = projected_width(normal, 480.0, 580.0);
+        let zoomed_width = projected_width(zoomed, 480.0, 580.0);
+
+        assert!((zoomed_width - 2.0 * normal_width).abs() < 1e-5);
+    }
+
+    #[test]
+    fn offset_moves_view_and_invalid_zoom_is_ignored() {
+        let view = ViewAdjust { zoom: 0.0, offset: Vec2::new(100.0, 0.0), rotation: 0.0 };
+        let projection = orthographic_projection(960.0, 540.0, &view);
+
+        // The centre of the shifted view lands in the middle of the screen
+        assert!(projection.transform_point3(Vec3::new(580.0, 270.0, 0.0)).truncate().abs_diff_eq(Vec2::ZERO, 1e-6));
+    }
+
+    #[test]
+    fn rotation_turns_the_view_about_its_centre() {
+        let view = ViewAdjust { rotation: std::f32::consts::FRAC_PI_2, ..Default::default() };
+        let projection = orthographic_projection(960.0, 540.0, &view);
+
+        // The centre stays put while a point to its right ends up below it
+        assert!(projection.transform_point3(Vec3::new(480.0, 270.0, 0.0)).truncate().abs_diff_eq(Vec2::ZERO, 1e-6));
+        assert!(projection.transform_point3(Vec3::new(480.0 + 135.0, 270.0, 0.0)).truncate().abs_diff_eq(Vec2::new(0.0, -0.5), 1e-5));
+    }
+
+    fn in_ndc(projection: Mat4, point: Vec2) -> bool {
+        let ndc = projection.transform_point3(Vec3::new(point.x, point.y, 0.0));
+
+        ndc.x.abs() <= 1.0 + 1e-5 && ndc.y.abs() <= 1.0 + 1e-5
+    }
+
+    #[test]
+    fn fit_keeps_points_on_screen_with_padding() {
+        let players = [Vec2::new(100.0, 400.0), Vec2::new(1800.0, 150.0)];
+        let view = ViewAdjust::fit(960.0, 540.0, &players, 50.0);
+        let projection = orthographic_projection(960.0, 540.0, &view);
+
+        for player in &players {
+            for margin in &[Vec2::new(50.0, 0.0), Vec2::new(-50.0, 0.0), Vec2::new(0.0, 50.0), Vec2::new(0.0, -50.0)] {
+                assert!(in_ndc(projection, *player + *margin));
+            }
+        }
+
+        // The wider spread decides the zoom, so the padded pair spans the screen edge to edge
+        let left = projection.transform_point3(Vec3::new(50.0, 275.0, 0.0));
+        let right = projection.transform_point3(Vec3::new(1850.0, 275.0, 0.0));
+        assert!((left.x + 1.0).abs() < 1e-5 && (right.x - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn fit_single_point_centres_without_zooming() {
+        let view = ViewAdjust::fit(960.0, 540.0, &[Vec2::new(600.0, 300.0)], 0.0);
+
+        assert_eq!(view, ViewAdjust { zoom: 1.0, offset: Vec2::new(120.0, 30.0), rotation: 0.0 });
+        assert_eq!(ViewAdjust::fit(960.0, 540.0, &[], 10.0), ViewAdjust::default());
+    }
+}